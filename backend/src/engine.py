@@ -1,6 +1,8 @@
 
 import io
 import os
+import threading
+from typing import Optional
 import torch
 import soundfile as sf
 try:
@@ -38,9 +40,15 @@ except ImportError:
     ChatterboxTTS = MockChatterboxTTS
     ChatterboxTurboTTS = MockChatterboxTurboTTS
 
+# Models a request can pick by name
+MODELS = {
+    "chatterbox-turbo": ChatterboxTurboTTS,
+    "chatterbox": ChatterboxTTS,
+}
+DEFAULT_MODEL = "chatterbox-turbo"
+
 class SynthesisEngine:
     _instance = None
-    _model = None
 
     @classmethod
     def get_instance(cls):
@@ -49,17 +57,35 @@ class SynthesisEngine:
         return cls._instance
 
     def __init__(self):
-        if self._model is None:
-            # Determine device
-            device = "cuda" if torch.cuda.is_available() else "cpu"
-            print(f"Initializing SynthesisEngine on {device}...")
-            
-            # Load model
-            print(f"Initializing SynthesisEngine using Chatterbox-Turbo (350M)...")
-            self._model = ChatterboxTurboTTS.from_pretrained(device)
-            print(f"SynthesisEngine initialized. Sample Rate: {self._model.sr}")
+        # Determine device
+        self._device = "cuda" if torch.cuda.is_available() else "cpu"
+        print(f"Initializing SynthesisEngine on {self._device}...")
+        self._models = {}
+        # Seeding sets torch's global RNG, so seeded generations run one at a time
+        self._seed_lock = threading.Lock()
+
+        # Load the default model up front; others load on first use
+        print(f"Initializing SynthesisEngine using Chatterbox-Turbo (350M)...")
+        model = self._get_model(DEFAULT_MODEL)
+        print(f"SynthesisEngine initialized. Sample Rate: {model.sr}")
 
-    def generate(self, text: str, reference_audio_path: str, cfg_weight: float = 0.5, exaggeration: float = 0.5) -> io.BytesIO:
+    def _get_model(self, name: str):
+        if name not in MODELS:
+            raise ValueError(f"Unknown model: {name}. Available models: {', '.join(MODELS)}")
+        if name not in self._models:
+            print(f"Loading model {name}...")
+            self._models[name] = MODELS[name].from_pretrained(self._device)
+        return self._models[name]
+
+    def generate(
+        self,
+        text: str,
+        reference_audio_path: str,
+        cfg_weight: float = 0.5,
+        exaggeration: float = 0.5,
+        model: str = DEFAULT_MODEL,
+        seed: Optional[int] = None,
+    ) -> io.BytesIO:
         """
         Generates audio from text using the reference audio for voice cloning/style.
         
@@ -68,29 +94,41 @@ class SynthesisEngine:
             reference_audio_path: Path to the reference audio file
             cfg_weight: Classifier-free guidance weight (default: 0.5)
             exaggeration: Exaggeration level for expressive speech (default: 0.5)
+            model: Name of the model to use, one of MODELS
+            seed: Sampling seed for repeatable output; random when None
             
         Returns a BytesIO object containing the WAV data.
         """
         if not os.path.exists(reference_audio_path):
             raise FileNotFoundError(f"Reference audio not found: {reference_audio_path}")
 
+        tts = self._get_model(model)
+
         # Generate audio
         # audio_prompt_path argument maps to reference_audio_path
         # The model returns a tensor of shape (1, samples)
-        with torch.no_grad():
-            output_tensor = self._model.generate(
-                text, 
-                audio_prompt_path=reference_audio_path,
-                cfg_weight=cfg_weight,
-                exaggeration=exaggeration
-            )
+        def run():
+            with torch.no_grad():
+                return tts.generate(
+                    text, 
+                    audio_prompt_path=reference_audio_path,
+                    cfg_weight=cfg_weight,
+                    exaggeration=exaggeration
+                )
+
+        if seed is None:
+            output_tensor = run()
+        else:
+            with self._seed_lock:
+                torch.manual_seed(seed)
+                output_tensor = run()
 
         # Convert to numpy and squeeze to (samples,)
         audio_data = output_tensor.squeeze().cpu().numpy()
 
         # Write to WAV in memory
         buffer = io.BytesIO()
-        sf.write(buffer, audio_data, self._model.sr, format='WAV')
+        sf.write(buffer, audio_data, tts.sr, format='WAV')
         buffer.seek(0)
         
         return buffer
@@ -1,9 +1,10 @@
 from fastapi import FastAPI, HTTPException
 from fastapi.responses import StreamingResponse
 from fastapi.middleware.cors import CORSMiddleware
+from typing import Optional
 from pydantic import BaseModel
-from .engine import SynthesisEngine
-from .routers import youtube, settings, files, projects, characters, history, audio
+from .engine import SynthesisEngine, DEFAULT_MODEL
+from .routers import youtube, settings, files, projects, characters, history, audio, project_config
 from .services.settings_service import DATA_DIR
 from .services.project_service import ProjectService
 
@@ -31,12 +32,15 @@ app.include_router(projects.router, tags=["projects"])
 app.include_router(characters.router, tags=["characters"])
 app.include_router(history.router, tags=["history"])
 app.include_router(audio.router, tags=["audio"])
+app.include_router(project_config.router, tags=["project-config"])
 
 class SynthesisRequest(BaseModel):
     text: str
     reference_audio_path: str
     cfg_weight: float = 0.5
     exaggeration: float = 0.5
+    model: str = DEFAULT_MODEL
+    seed: Optional[int] = None
 
 @app.get("/health")
 def health():
@@ -56,10 +60,12 @@ def synthesize(request: SynthesisRequest):
             request.text, 
             request.reference_audio_path,
             cfg_weight=request.cfg_weight,
-            exaggeration=request.exaggeration
+            exaggeration=request.exaggeration,
+            model=request.model,
+            seed=request.seed
         )
         return StreamingResponse(audio_buffer, media_type="audio/wav")
-    except FileNotFoundError as e:
+    except (FileNotFoundError, ValueError) as e:
         raise HTTPException(status_code=400, detail=str(e))
     except Exception as e:
         print(f"Synthesis failed: {e}")
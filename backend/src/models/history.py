@@ -11,6 +11,7 @@ class ScriptHistory(BaseModel):
     created_at: datetime
     character_mappings: Dict[str, str]  # character_name -> character_id
     processed_from: Optional[str] = None  # ID of original history entry if this is a processed file
+    variables: Dict[str, str] = {}  # {{name}} values the script was rendered with
 
     @classmethod
     def create(
@@ -18,7 +19,8 @@ class ScriptHistory(BaseModel):
         name: str,
         script_text: str,
         audio_path: str,
-        character_mappings: Dict[str, str]
+        character_mappings: Dict[str, str],
+        variables: Optional[Dict[str, str]] = None
     ) -> "ScriptHistory":
         return cls(
             id=str(uuid.uuid4()),
@@ -26,5 +28,6 @@ class ScriptHistory(BaseModel):
             script_text=script_text,
             audio_path=audio_path,
             created_at=datetime.now(timezone.utc),
-            character_mappings=character_mappings
+            character_mappings=character_mappings,
+            variables=variables or {}
         )
@@ -1,5 +1,5 @@
-from pydantic import BaseModel
-from typing import Optional
+from pydantic import BaseModel, Field
+from typing import Dict, List, Optional
 from datetime import datetime, timezone
 import uuid
 
@@ -17,3 +17,21 @@ class Project(BaseModel):
             base_path=base_path,
             created_at=datetime.now(timezone.utc)
         )
+
+
+class PronunciationRule(BaseModel):
+    """A word and the respelling the TTS model should read instead."""
+    id: str = Field(default_factory=lambda: str(uuid.uuid4()))
+    word: str
+    respelling: str
+    whole_word: bool = True
+    case_sensitive: bool = False
+
+
+class ProjectConfig(BaseModel):
+    """Per-project script and synthesis preferences."""
+    narrator_character_id: Optional[str] = None
+    narrate_unmarked_lines: bool = False
+    pronunciations: List[PronunciationRule] = []
+    normalize_text: bool = False
+    variables: Dict[str, str] = {}
@@ -8,6 +8,11 @@ class Settings(BaseModel):
     voice_files_directory: str
     projects_directory: str
     active_project_id: Optional[str] = None
+    max_chunk_chars: int = 300
+    cache_limit_mb: int = 1024
+    max_concurrent_jobs: int = 1
+    max_retries: int = 2
+    continue_on_error: bool = False
     
     @staticmethod
     def get_default_settings(project_root: str) -> "Settings":
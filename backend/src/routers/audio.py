@@ -133,7 +133,8 @@ async def process_audio(request: AudioProcessRequest):
             name=processed_name,
             script_text=history_entry.script_text,
             audio_path=os.path.join("outputs", output_filename),
-            character_mappings=history_entry.character_mappings
+            character_mappings=history_entry.character_mappings,
+            variables=history_entry.variables
         )
         new_history.processed_from = request.history_id
         
@@ -1,5 +1,5 @@
 import logging
-from typing import List
+from typing import List, Optional
 from fastapi import APIRouter, HTTPException
 from ..services.character_service import Character, load_characters, save_characters
 
@@ -10,8 +10,9 @@ router = APIRouter()
 # In a cleaner architecture, this would be in models/character.py
 
 @router.get("/characters", response_model=List[Character])
-def list_characters():
-    return load_characters()
+def list_characters(project_id: Optional[str] = None):
+    """Get the characters of the given project, or of the active project."""
+    return load_characters(project_id)
 
 @router.post("/characters", response_model=List[Character])
 def update_characters(characters: List[Character]):
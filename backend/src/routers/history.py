@@ -14,6 +14,7 @@ class CreateHistoryRequest(BaseModel):
     script_text: str
     audio_path: str
     character_mappings: Dict[str, str]
+    variables: Dict[str, str] = {}
 
 
 @router.get("/history", response_model=List[ScriptHistory])
@@ -37,7 +38,8 @@ def create_history_entry(
             name=request.name,
             script_text=request.script_text,
             audio_path=request.audio_path,
-            character_mappings=request.character_mappings
+            character_mappings=request.character_mappings,
+            variables=request.variables
         )
         return service.save_history_entry(entry)
     except Exception as e:
@@ -0,0 +1,25 @@
+import logging
+from typing import Optional
+from fastapi import APIRouter, HTTPException
+from ..models.project import ProjectConfig
+from ..services.project_config_service import load_project_config, save_project_config
+
+logger = logging.getLogger(__name__)
+router = APIRouter()
+
+@router.get("/project-config", response_model=ProjectConfig)
+def get_project_config(project_id: Optional[str] = None):
+    """Get the script/synthesis preferences of the given project, or of the active project."""
+    return load_project_config(project_id)
+
+@router.post("/project-config", response_model=ProjectConfig)
+def update_project_config(config: ProjectConfig):
+    """Update the script/synthesis preferences of the active project."""
+    try:
+        save_project_config(config)
+        return config
+    except ValueError as e:
+        raise HTTPException(status_code=400, detail=str(e))
+    except Exception as e:
+        logger.error(f"Failed to update project config: {e}")
+        raise HTTPException(status_code=500, detail=str(e))
@@ -7,11 +7,20 @@ from .settings_service import load_settings, save_settings, PROJECT_ROOT
 
 logger = logging.getLogger(__name__)
 
+class VoiceDefaults(BaseModel):
+    """Synthesis parameters for a character's lines, used unless a line sets its own."""
+    cfg_weight: Optional[float] = None
+    exaggeration: Optional[float] = None
+    model: Optional[str] = None
+    seed: Optional[int] = None
+
 class Character(BaseModel):
     id: str
     name: str
     description: Optional[str] = ""
     voice_reference_path: Optional[str] = ""
+    aliases: List[str] = []
+    synthesis_defaults: VoiceDefaults = VoiceDefaults()
 
 def get_characters_file(project_id: Optional[str] = None) -> str:
     if not project_id:
@@ -20,9 +29,10 @@ def get_characters_file(project_id: Optional[str] = None) -> str:
     
     return os.path.join(PROJECT_ROOT, "frontend", "data", "projects", project_id, "characters.json")
 
-def load_characters() -> List[Character]:
-    settings = load_settings()
-    filepath = get_characters_file(settings.active_project_id)
+def load_characters(project_id: Optional[str] = None) -> List[Character]:
+    if not project_id:
+        project_id = load_settings().active_project_id
+    filepath = get_characters_file(project_id)
     
     if os.path.exists(filepath):
         try:
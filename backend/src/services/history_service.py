@@ -99,18 +99,19 @@ class HistoryService:
             raise
 
 
-def get_history_service() -> HistoryService:
-    """Get history service for the active project."""
+def get_history_service(project_id: Optional[str] = None) -> HistoryService:
+    """Get history service for the given project, or for the active project."""
     from .project_service import ProjectService
     
     settings = load_settings()
-    if not settings.active_project_id:
+    project_id = project_id or settings.active_project_id
+    if not project_id:
         raise ValueError("No active project")
     
     project_service = ProjectService(settings.projects_directory)
-    project = project_service.get_project(settings.active_project_id)
+    project = project_service.get_project(project_id)
     
     if not project:
-        raise ValueError(f"Active project not found: {settings.active_project_id}")
+        raise ValueError(f"Project not found: {project_id}")
     
     return HistoryService(project.base_path)
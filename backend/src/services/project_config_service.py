@@ -0,0 +1,41 @@
+import os
+import json
+import logging
+from typing import Optional
+from ..models.project import ProjectConfig
+from .settings_service import load_settings, PROJECT_ROOT
+
+logger = logging.getLogger(__name__)
+
+def get_project_config_file(project_id: Optional[str] = None) -> Optional[str]:
+    if not project_id:
+        return None
+
+    return os.path.join(PROJECT_ROOT, "frontend", "data", "projects", project_id, "project_config.json")
+
+def load_project_config(project_id: Optional[str] = None) -> ProjectConfig:
+    if not project_id:
+        project_id = load_settings().active_project_id
+    filepath = get_project_config_file(project_id)
+
+    if filepath and os.path.exists(filepath):
+        try:
+            with open(filepath, 'r') as f:
+                return ProjectConfig(**json.load(f))
+        except Exception as e:
+            logger.error(f"Failed to load project config from {filepath}: {e}")
+    return ProjectConfig()
+
+def save_project_config(config: ProjectConfig) -> None:
+    settings = load_settings()
+    filepath = get_project_config_file(settings.active_project_id)
+    if not filepath:
+        raise ValueError("No active project")
+
+    os.makedirs(os.path.dirname(filepath), exist_ok=True)
+    try:
+        with open(filepath, 'w') as f:
+            json.dump(config.dict(), f, indent=2)
+    except Exception as e:
+        logger.error(f"Failed to save project config to {filepath}: {e}")
+        raise
@@ -0,0 +1,35 @@
+"""Smoke tests for the persisted models.
+
+Run from the backend directory with `python -m unittest discover tests`.
+"""
+import unittest
+
+from src.models.history import ScriptHistory  # noqa: F401  imported to check it loads
+from src.models.project import ProjectConfig, PronunciationRule
+from src.models.settings import Settings
+
+
+class ProjectConfigTest(unittest.TestCase):
+    def test_defaults(self):
+        config = ProjectConfig()
+        self.assertEqual(config.variables, {})
+        self.assertEqual(config.pronunciations, [])
+
+    def test_round_trip(self):
+        config = ProjectConfig(
+            pronunciations=[PronunciationRule(word="Drizzt", respelling="Drizzit")],
+            variables={"campaign": "Curse of Strahd"},
+        )
+        restored = ProjectConfig(**config.dict())
+        self.assertEqual(restored, config)
+
+
+class SettingsTest(unittest.TestCase):
+    def test_new_fields_default_when_missing(self):
+        settings = Settings(output_directory="out", voice_files_directory="voices", projects_directory="projects")
+        self.assertEqual(settings.max_concurrent_jobs, 1)
+        self.assertFalse(settings.continue_on_error)
+
+
+if __name__ == "__main__":
+    unittest.main()
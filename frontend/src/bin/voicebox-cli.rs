@@ -0,0 +1,421 @@
+//! Command-line front end to the VoiceBox backend: renders a script to a single WAV
+//! file without the desktop app, and lists projects, characters and history.
+
+use frontend::models::script::{ScriptLine, Severity};
+use frontend::services::api::{
+    check_backend_health, fetch_characters, fetch_project_config, fetch_projects, fetch_settings,
+};
+use frontend::services::history::fetch_history;
+use frontend::services::run_control::RunControl;
+use frontend::services::script_parser::{parse_script_with_options, ParseOptions};
+use frontend::services::subtitles::write_subtitles;
+use frontend::services::synthesis_pipeline::{
+    collect_segments, validate_lines, PipelineEvent, RunOutcome, RunStatus, SynthesisPipeline,
+};
+use frontend::utils::audio::combine_segments;
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::str::FromStr;
+
+const USAGE: &str = "\
+Usage: voicebox-cli <command> [options]
+
+Commands:
+  synth <script>     Synthesize a script into one WAV file
+  projects           List projects; the active one is marked with *
+  characters         List the characters of a project
+  history            List the saved scripts of a project
+
+Options:
+  --project <name>   Project to use, by name or ID (default: the active project).
+                     The backend's active project, shared with the desktop app, is left as is
+  --out <file>       Output WAV file (synth; default: the script path with .wav)
+  --cfg <weight>     CFG weight for lines without an override or character default (synth)
+  --exaggeration <x> Exaggeration for lines without an override or character default (synth)
+  --jobs <n>         Lines synthesized at the same time (synth; default: settings)
+  --continue-on-error
+                     Keep going past failed lines and export the rest (synth)
+  --subtitles        Also write .srt and .vtt subtitles next to the output (synth)
+  -h, --help         Show this help
+
+Exit codes:
+  0    success
+  1    synthesis or export failed
+  2    invalid arguments, unreadable script or unknown project
+  3    the script has errors
+  4    the backend is unreachable or returned an error
+  130  cancelled with Ctrl-C";
+
+const EXIT_FAILED: u8 = 1;
+const EXIT_USAGE: u8 = 2;
+const EXIT_SCRIPT_ERRORS: u8 = 3;
+const EXIT_BACKEND: u8 = 4;
+const EXIT_CANCELLED: u8 = 130;
+
+#[derive(Debug, Clone, PartialEq)]
+enum Command {
+    Synth(SynthArgs),
+    Projects,
+    Characters,
+    History,
+    Help,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+struct SynthArgs {
+    script: PathBuf,
+    out: Option<PathBuf>,
+    cfg_weight: Option<f32>,
+    exaggeration: Option<f32>,
+    jobs: Option<usize>,
+    continue_on_error: bool,
+    subtitles: bool,
+}
+
+/// A parsed command line: the command and the project it runs against.
+#[derive(Debug, Clone, PartialEq)]
+struct Invocation {
+    command: Command,
+    project: Option<String>,
+}
+
+/// Why a command failed, and the exit code that reports it.
+struct Failure {
+    code: u8,
+    message: String,
+}
+
+impl Failure {
+    fn new(code: u8, message: impl Into<String>) -> Self {
+        Self { code, message: message.into() }
+    }
+
+    fn backend(message: String) -> Self {
+        Self::new(EXIT_BACKEND, message)
+    }
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    let invocation = match parse_args(&args) {
+        Ok(invocation) => invocation,
+        Err(e) => {
+            eprintln!("error: {}\n\n{}", e, USAGE);
+            return ExitCode::from(EXIT_USAGE);
+        }
+    };
+    if invocation.command == Command::Help {
+        println!("{}", USAGE);
+        return ExitCode::SUCCESS;
+    }
+
+    if !check_backend_health().await {
+        eprintln!("error: the VoiceBox backend is not running; start it with `uvicorn src.main:app`");
+        return ExitCode::from(EXIT_BACKEND);
+    }
+
+    match run(invocation).await {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(failure) => {
+            eprintln!("error: {}", failure.message);
+            ExitCode::from(failure.code)
+        }
+    }
+}
+
+/// Runs the command against the requested project. The project is passed on each
+/// request, so the backend's active project, which the desktop app shares, never
+/// changes.
+async fn run(invocation: Invocation) -> Result<(), Failure> {
+    let project_id = match &invocation.project {
+        Some(wanted) => Some(find_project(wanted).await?),
+        None => None,
+    };
+    let project_id = project_id.as_deref();
+
+    match invocation.command {
+        Command::Synth(args) => synth(args, project_id).await,
+        Command::Projects => list_projects().await,
+        Command::Characters => list_characters(project_id).await,
+        Command::History => list_history(project_id).await,
+        Command::Help => Ok(()),
+    }
+}
+
+/// ID of the project named or identified by `wanted`.
+async fn find_project(wanted: &str) -> Result<String, Failure> {
+    let projects = fetch_projects().await.map_err(Failure::backend)?;
+    let Some(project) = projects
+        .iter()
+        .find(|p| p.id == wanted)
+        .or_else(|| projects.iter().find(|p| p.name.to_lowercase() == wanted.to_lowercase()))
+    else {
+        let names: Vec<&str> = projects.iter().map(|p| p.name.as_str()).collect();
+        return Err(Failure::new(
+            EXIT_USAGE,
+            format!("Unknown project '{}'; projects are: {}", wanted, names.join(", ")),
+        ));
+    };
+    Ok(project.id.clone())
+}
+
+async fn synth(args: SynthArgs, project_id: Option<&str>) -> Result<(), Failure> {
+    let text = std::fs::read_to_string(&args.script)
+        .map_err(|e| Failure::new(EXIT_USAGE, format!("Failed to read {}: {}", args.script.display(), e)))?;
+
+    let characters = fetch_characters(project_id).await.map_err(Failure::backend)?;
+    let project = fetch_project_config(project_id).await.map_err(Failure::backend)?;
+    let mut settings = fetch_settings().await.map_err(Failure::backend)?;
+    // Only this run's copy: clips go to the requested project's run directory
+    if let Some(project_id) = project_id {
+        settings.active_project_id = Some(project_id.to_string());
+    }
+    if let Some(jobs) = args.jobs {
+        settings.max_concurrent_jobs = jobs;
+    }
+    settings.continue_on_error |= args.continue_on_error;
+
+    let parsed = parse_script_with_options(&text, &characters, &ParseOptions::from_config(&project));
+    for diagnostic in &parsed.diagnostics {
+        let severity = match diagnostic.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        eprintln!(
+            "{}:{}:{}: {}: {}",
+            args.script.display(),
+            diagnostic.line,
+            diagnostic.span.start + 1,
+            severity,
+            diagnostic.message
+        );
+    }
+    if parsed.has_errors() {
+        return Err(Failure::new(EXIT_SCRIPT_ERRORS, "The script has errors; nothing was synthesized"));
+    }
+
+    let mut lines = parsed.lines;
+    validate_lines(&lines).map_err(|e| Failure::new(EXIT_SCRIPT_ERRORS, e))?;
+
+    let pipeline = SynthesisPipeline::new(lines.clone(), 0..lines.len(), characters, settings, project)
+        .map_err(|e| Failure::new(EXIT_FAILED, e))?
+        .with_parameters(args.cfg_weight.unwrap_or(0.5), args.exaggeration.unwrap_or(0.5));
+    let total = pipeline.pending().len();
+    if total == 0 {
+        return Err(Failure::new(EXIT_SCRIPT_ERRORS, "The script has no lines to synthesize"));
+    }
+    eprintln!(
+        "Synthesizing {} line(s) from {} into {}",
+        total,
+        args.script.display(),
+        pipeline.run_dir().display()
+    );
+
+    // Ctrl-C cancels the run, so the manifest is still written
+    let control = RunControl::new();
+    let on_interrupt = control.clone();
+    tokio::spawn(async move {
+        if tokio::signal::ctrl_c().await.is_ok() {
+            on_interrupt.cancel();
+        }
+    });
+
+    let outcome = report_progress(pipeline, control, &mut lines, total).await;
+    let failed = match outcome {
+        RunOutcome::Completed => 0,
+        RunOutcome::CompletedWithFailures(failed) => failed,
+        RunOutcome::Stopped(error) => return Err(Failure::new(EXIT_FAILED, error)),
+        RunOutcome::Cancelled => return Err(Failure::new(EXIT_CANCELLED, "Synthesis cancelled")),
+    };
+
+    let out = args.out.unwrap_or_else(|| args.script.with_extension("wav"));
+    combine_segments(collect_segments(&lines), out.clone())
+        .map_err(|e| Failure::new(EXIT_FAILED, format!("Export failed: {}", e)))?;
+    if args.subtitles {
+        write_subtitles(&lines, &out).map_err(|e| Failure::new(EXIT_FAILED, format!("Subtitles failed: {}", e)))?;
+    }
+
+    if failed > 0 {
+        return Err(Failure::new(
+            EXIT_FAILED,
+            format!("Wrote {} without {} failed line(s)", out.display(), failed),
+        ));
+    }
+    eprintln!("Wrote {}", out.display());
+    Ok(())
+}
+
+/// Runs the pipeline to the end, printing a line per finished script line to
+/// stderr, and returns how it ended. `lines` receive the output paths.
+async fn report_progress(
+    pipeline: SynthesisPipeline,
+    control: RunControl,
+    lines: &mut [ScriptLine],
+    total: usize,
+) -> RunOutcome {
+    let mut status = RunStatus::started(control.clone(), 0);
+    let mut events = pipeline.spawn(control);
+    let mut finished = 0;
+    let mut outcome = RunOutcome::Cancelled;
+
+    while let Some(event) = events.recv().await {
+        match &event {
+            PipelineEvent::Done { index, .. } => {
+                finished += 1;
+                eprintln!("[{}/{}] line {} ({}) done", finished, total, index + 1, lines[*index].character_name);
+            }
+            PipelineEvent::Failed { index, reason, .. } => {
+                finished += 1;
+                eprintln!(
+                    "[{}/{}] line {} ({}) failed: {}",
+                    finished,
+                    total,
+                    index + 1,
+                    lines[*index].character_name,
+                    reason
+                );
+            }
+            PipelineEvent::Finished(end) => outcome = end.clone(),
+            PipelineEvent::Queued { .. } | PipelineEvent::Started { .. } => {}
+        }
+        status.apply(&event, lines);
+    }
+    outcome
+}
+
+async fn list_projects() -> Result<(), Failure> {
+    let projects = fetch_projects().await.map_err(Failure::backend)?;
+    let settings = fetch_settings().await.map_err(Failure::backend)?;
+    for project in projects {
+        let marker = if settings.active_project_id.as_deref() == Some(project.id.as_str()) { "*" } else { " " };
+        println!("{} {}\t{}", marker, project.name, project.id);
+    }
+    Ok(())
+}
+
+async fn list_characters(project_id: Option<&str>) -> Result<(), Failure> {
+    for character in fetch_characters(project_id).await.map_err(Failure::backend)? {
+        let voice = character.voice_path.as_deref().unwrap_or("(no voice)");
+        let aliases = if character.aliases.is_empty() {
+            String::new()
+        } else {
+            format!(" (aka {})", character.aliases.join(", "))
+        };
+        println!("{}{}\t{}", character.name, aliases, voice);
+    }
+    Ok(())
+}
+
+async fn list_history(project_id: Option<&str>) -> Result<(), Failure> {
+    let mut history = fetch_history(project_id).await.map_err(Failure::backend)?;
+    history.sort_by_key(|entry| std::cmp::Reverse(entry.created_at));
+    for entry in history {
+        println!("{}\t{}\t{}", entry.created_at.format("%Y-%m-%d %H:%M"), entry.name, entry.audio_path);
+    }
+    Ok(())
+}
+
+fn parse_args(args: &[String]) -> Result<Invocation, String> {
+    let mut positional = Vec::new();
+    let mut project = None;
+    let mut synth = SynthArgs::default();
+    // Options only `synth` accepts, to reject them for the other commands
+    let mut synth_options = Vec::new();
+
+    let mut iter = args.iter();
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(Invocation { command: Command::Help, project: None }),
+            "--project" => project = Some(value(&mut iter, arg)?),
+            "--out" => synth.out = Some(PathBuf::from(value(&mut iter, arg)?)),
+            "--cfg" => synth.cfg_weight = Some(number(&mut iter, arg)?),
+            "--exaggeration" => synth.exaggeration = Some(number(&mut iter, arg)?),
+            "--jobs" => {
+                let jobs = number(&mut iter, arg)?;
+                if jobs == 0 {
+                    return Err("--jobs must be at least 1".to_string());
+                }
+                synth.jobs = Some(jobs);
+            }
+            "--continue-on-error" => synth.continue_on_error = true,
+            "--subtitles" => synth.subtitles = true,
+            _ if arg.starts_with('-') => return Err(format!("Unknown option {}", arg)),
+            _ => positional.push(arg.as_str()),
+        }
+        if arg != "--project" && arg.starts_with("--") {
+            synth_options.push(arg.as_str());
+        }
+    }
+
+    let command = match positional.as_slice() {
+        ["synth", script] => {
+            synth.script = PathBuf::from(script);
+            Command::Synth(synth)
+        }
+        ["synth"] => return Err("synth needs a script file".to_string()),
+        ["projects"] => Command::Projects,
+        ["characters"] => Command::Characters,
+        ["history"] => Command::History,
+        [] => return Err("Missing command".to_string()),
+        [command @ ("synth" | "projects" | "characters" | "history"), extra, ..] => {
+            return Err(format!("Unexpected argument '{}' for {}", extra, command));
+        }
+        [command, ..] => return Err(format!("Unknown command '{}'", command)),
+    };
+
+    if !matches!(command, Command::Synth(_)) {
+        if let Some(option) = synth_options.first() {
+            return Err(format!("{} only applies to synth", option));
+        }
+        if command == Command::Projects && project.is_some() {
+            return Err("--project doesn't apply to projects".to_string());
+        }
+    }
+    Ok(Invocation { command, project })
+}
+
+fn value<'a>(iter: &mut impl Iterator<Item = &'a String>, option: &str) -> Result<String, String> {
+    iter.next().cloned().ok_or_else(|| format!("{} needs a value", option))
+}
+
+fn number<'a, T: FromStr>(iter: &mut impl Iterator<Item = &'a String>, option: &str) -> Result<T, String> {
+    let raw = value(iter, option)?;
+    raw.parse().map_err(|_| format!("Invalid value '{}' for {}", raw, option))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(line: &str) -> Vec<String> {
+        line.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn test_parse_args() {
+        let invocation = parse_args(&args("synth recap.txt --project EveOfRuin --out recap.wav --cfg 0.3")).unwrap();
+        assert_eq!(invocation.project.as_deref(), Some("EveOfRuin"));
+        assert_eq!(
+            invocation.command,
+            Command::Synth(SynthArgs {
+                script: PathBuf::from("recap.txt"),
+                out: Some(PathBuf::from("recap.wav")),
+                cfg_weight: Some(0.3),
+                ..SynthArgs::default()
+            })
+        );
+
+        assert_eq!(parse_args(&args("history --project EveOfRuin")).unwrap().command, Command::History);
+        assert_eq!(parse_args(&args("synth a.txt --help")).unwrap().command, Command::Help);
+
+        assert!(parse_args(&args("synth")).is_err());
+        assert!(parse_args(&args("synth a.txt b.txt")).is_err());
+        assert!(parse_args(&args("synth a.txt --cfg high")).is_err());
+        assert!(parse_args(&args("synth a.txt --jobs 0")).is_err());
+        assert!(parse_args(&args("characters --out x.wav")).is_err());
+        assert!(parse_args(&args("projects --project EveOfRuin")).is_err());
+        assert!(parse_args(&args("render a.txt")).is_err());
+        assert!(parse_args(&args("")).is_err());
+    }
+}
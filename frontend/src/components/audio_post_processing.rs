@@ -23,7 +23,7 @@ pub fn AudioPostProcessing() -> Element {
     // Fetch history on mount
     use_effect(move || {
         spawn(async move {
-            if let Ok(entries) = fetch_history().await {
+            if let Ok(entries) = fetch_history(None).await {
                 history_entries.set(entries);
             }
         });
@@ -150,7 +150,7 @@ pub fn AudioPostProcessing() -> Element {
                     error_message.set(None);
                     
                     // Refresh history
-                    if let Ok(entries) = fetch_history().await {
+                    if let Ok(entries) = fetch_history(None).await {
                         history_entries.set(entries);
                     }
                 }
@@ -1,5 +1,6 @@
 use dioxus::prelude::*;
 use crate::models::character::Character;
+use crate::services::api::{SYNTHESIS_MODEL, SYNTHESIS_MODELS};
 use crate::components::youtube_import::YouTubeImport;
 use crate::components::voice_file_list::VoiceFileList;
 
@@ -11,6 +12,7 @@ pub fn Editor(
 ) -> Element {
     let char = selected_char;
     let char_id = char.id.clone();
+    let mut new_alias = use_signal(String::new);
     
     rsx! {
         div {
@@ -32,6 +34,58 @@ pub fn Editor(
                 }
             }
 
+            div {
+                label { "Aliases" }
+                div {
+                    style: "display: flex; flex-wrap: wrap; gap: 6px; margin: 5px 0;",
+                    for (index, alias) in char.aliases.iter().enumerate() {
+                        span {
+                            key: "{alias}",
+                            style: "background-color: #e9ecef; padding: 2px 8px; border-radius: 12px; font-size: 12px; display: flex; align-items: center; gap: 4px;",
+                            "{alias}"
+                            button {
+                                style: "border: none; background: none; cursor: pointer; color: #dc3545; padding: 0;",
+                                title: "Remove alias",
+                                onclick: {
+                                    let char = char.clone();
+                                    move |_| {
+                                        let mut c = char.clone();
+                                        c.aliases.remove(index);
+                                        on_update.call(c);
+                                    }
+                                },
+                                "×"
+                            }
+                        }
+                    }
+                }
+                div {
+                    style: "display: flex; gap: 10px; align-items: center;",
+                    input {
+                        style: "flex-grow: 1;",
+                        placeholder: "e.g. Gandalf the Grey",
+                        value: "{new_alias}",
+                        oninput: move |e: FormEvent| new_alias.set(e.value()),
+                    }
+                    button {
+                        onclick: {
+                            let char = char.clone();
+                            move |_| {
+                                let alias = new_alias().trim().to_string();
+                                if alias.is_empty() || char.aliases.contains(&alias) {
+                                    return;
+                                }
+                                let mut c = char.clone();
+                                c.aliases.push(alias);
+                                on_update.call(c);
+                                new_alias.set(String::new());
+                            }
+                        },
+                        "Add Alias"
+                    }
+                }
+            }
+
             div {
                 label { "Description" }
                 textarea {
@@ -47,6 +101,89 @@ pub fn Editor(
                 }
             }
 
+            div {
+                label { "Synthesis Defaults" }
+                p {
+                    style: "margin: 5px 0; font-size: 12px; color: #666;",
+                    "Used for this character's lines unless a line sets its own. Leave empty to use the script editor's sliders."
+                }
+                div {
+                    style: "display: grid; grid-template-columns: auto 1fr; gap: 8px 10px; align-items: center;",
+                    label { "CFG Weight" }
+                    input {
+                        r#type: "number",
+                        min: "0",
+                        max: "1",
+                        step: "0.05",
+                        placeholder: "Global",
+                        value: "{optional_value(char.synthesis_defaults.cfg_weight)}",
+                        oninput: {
+                            let char = char.clone();
+                            move |e: FormEvent| {
+                                if let Some(value) = parse_optional::<f32>(&e.value()) {
+                                    let mut c = char.clone();
+                                    c.synthesis_defaults.cfg_weight = value.map(|v| v.clamp(0.0, 1.0));
+                                    on_update.call(c);
+                                }
+                            }
+                        }
+                    }
+                    label { "Exaggeration" }
+                    input {
+                        r#type: "number",
+                        min: "0",
+                        max: "1",
+                        step: "0.05",
+                        placeholder: "Global",
+                        value: "{optional_value(char.synthesis_defaults.exaggeration)}",
+                        oninput: {
+                            let char = char.clone();
+                            move |e: FormEvent| {
+                                if let Some(value) = parse_optional::<f32>(&e.value()) {
+                                    let mut c = char.clone();
+                                    c.synthesis_defaults.exaggeration = value.map(|v| v.clamp(0.0, 1.0));
+                                    on_update.call(c);
+                                }
+                            }
+                        }
+                    }
+                    label { "Model" }
+                    select {
+                        value: "{char.synthesis_defaults.model.clone().unwrap_or_default()}",
+                        onchange: {
+                            let char = char.clone();
+                            move |e: FormEvent| {
+                                let mut c = char.clone();
+                                c.synthesis_defaults.model = Some(e.value()).filter(|m| !m.is_empty());
+                                on_update.call(c);
+                            }
+                        },
+                        option { value: "", "Default ({SYNTHESIS_MODEL})" }
+                        for model in SYNTHESIS_MODELS {
+                            option { value: "{model}", "{model}" }
+                        }
+                    }
+                    label { "Seed" }
+                    input {
+                        r#type: "number",
+                        min: "0",
+                        step: "1",
+                        placeholder: "Random",
+                        value: "{optional_value(char.synthesis_defaults.seed)}",
+                        oninput: {
+                            let char = char.clone();
+                            move |e: FormEvent| {
+                                if let Some(value) = parse_optional::<u64>(&e.value()) {
+                                    let mut c = char.clone();
+                                    c.synthesis_defaults.seed = value;
+                                    on_update.call(c);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+
             div {
                 label { "Voice Reference (File)" }
                 div {
@@ -131,3 +268,16 @@ pub fn Editor(
         }
     }
 }
+
+fn optional_value<T: ToString>(value: Option<T>) -> String {
+    value.map(|v| v.to_string()).unwrap_or_default()
+}
+
+/// Reads an optional number field: empty clears it, anything unparsable is ignored.
+fn parse_optional<T: std::str::FromStr>(input: &str) -> Option<Option<T>> {
+    let input = input.trim();
+    if input.is_empty() {
+        return Some(None);
+    }
+    input.parse().ok().map(Some)
+}
@@ -9,3 +9,4 @@ pub mod project_selector;
 pub mod progress_bar;
 pub mod script_history;
 pub mod audio_post_processing;
+pub mod pronunciation_dictionary;
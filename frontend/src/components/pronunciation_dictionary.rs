@@ -0,0 +1,223 @@
+use dioxus::prelude::*;
+use crate::models::project::{ProjectConfig, PronunciationRule};
+use crate::services::api::save_project_config;
+use crate::services::pronunciation::{apply_pronunciations, export_rules, import_rules};
+use rfd::FileDialog;
+
+/// Editor for the active project's pronunciation dictionary. Every change is saved
+/// to the project config right away.
+#[component]
+pub fn PronunciationDictionary(project_config: Signal<ProjectConfig>) -> Element {
+    let mut new_word = use_signal(String::new);
+    let mut new_respelling = use_signal(String::new);
+    let mut preview_text = use_signal(String::new);
+    let mut status = use_signal(|| None::<String>);
+
+    // Replace the rule list and persist it
+    let mut update_rules = move |rules: Vec<PronunciationRule>| {
+        let mut config = project_config();
+        config.pronunciations = rules;
+        project_config.set(config.clone());
+
+        spawn(async move {
+            if let Err(e) = save_project_config(config).await {
+                println!("❌ Failed to save pronunciation dictionary: {}", e);
+                status.set(Some(format!("Failed to save: {}", e)));
+            }
+        });
+    };
+
+    let on_add = move |_| {
+        let word = new_word().trim().to_string();
+        let respelling = new_respelling().trim().to_string();
+        if word.is_empty() || respelling.is_empty() {
+            status.set(Some("Enter both a word and its respelling.".to_string()));
+            return;
+        }
+
+        let mut rules = project_config().pronunciations;
+        rules.push(PronunciationRule::new(word, respelling));
+        update_rules(rules);
+        new_word.set(String::new());
+        new_respelling.set(String::new());
+        status.set(None);
+    };
+
+    let on_import = move |_| {
+        let file_dialog = FileDialog::new()
+            .set_title("Import Pronunciation Dictionary")
+            .add_filter("Dictionary", &["json", "csv"]);
+
+        if let Some(input_path) = file_dialog.pick_file() {
+            match import_rules(&input_path) {
+                Ok(imported) => {
+                    // Imported rules replace existing ones for the same word
+                    let mut rules = project_config().pronunciations;
+                    rules.retain(|r| !imported.iter().any(|i| i.word == r.word));
+                    let count = imported.len();
+                    rules.extend(imported);
+                    update_rules(rules);
+                    status.set(Some(format!("Imported {} rule(s).", count)));
+                }
+                Err(e) => status.set(Some(format!("Import failed: {}", e))),
+            }
+        }
+    };
+
+    let on_export = move |_| {
+        let rules = project_config().pronunciations;
+        let file_dialog = FileDialog::new()
+            .set_title("Export Pronunciation Dictionary")
+            .set_file_name("pronunciations.json")
+            .add_filter("JSON", &["json"])
+            .add_filter("CSV", &["csv"]);
+
+        if let Some(output_path) = file_dialog.save_file() {
+            match export_rules(&output_path, &rules) {
+                Ok(_) => status.set(Some(format!("Exported {} rule(s) to {}", rules.len(), output_path.display()))),
+                Err(e) => status.set(Some(format!("Export failed: {}", e))),
+            }
+        }
+    };
+
+    let rules = project_config().pronunciations;
+
+    rsx! {
+        div {
+            style: "background-color: white; padding: 20px; border-radius: 8px; box-shadow: 0 2px 4px rgba(0,0,0,0.1);",
+            div {
+                style: "display: flex; justify-content: space-between; align-items: center;",
+                h3 {
+                    style: "margin: 0; color: #333;",
+                    "Pronunciation Dictionary ({rules.len()})"
+                }
+                div {
+                    style: "display: flex; gap: 8px;",
+                    button {
+                        style: "padding: 6px 12px; background-color: #007bff; color: white; border: none; border-radius: 4px; cursor: pointer;",
+                        onclick: on_import,
+                        "Import..."
+                    }
+                    button {
+                        style: "padding: 6px 12px; background-color: #6c757d; color: white; border: none; border-radius: 4px; cursor: pointer;",
+                        disabled: rules.is_empty(),
+                        onclick: on_export,
+                        "Export..."
+                    }
+                }
+            }
+            p {
+                style: "margin: 8px 0 15px 0; font-size: 12px; color: #666;",
+                "Words are respelled only in the text sent to the voice model; the script itself is not changed. Applies to the active project."
+            }
+
+            if let Some(msg) = status() {
+                p {
+                    style: "margin: 0 0 10px 0; font-size: 13px; color: #555;",
+                    "{msg}"
+                }
+            }
+
+            // Rule list
+            for rule in rules.iter().cloned() {
+                div {
+                    key: "{rule.id}",
+                    style: "display: flex; align-items: center; gap: 10px; padding: 6px 0; border-bottom: 1px solid #eee;",
+                    span {
+                        style: "flex: 1; font-weight: bold; color: #333;",
+                        "{rule.word}"
+                    }
+                    span { style: "color: #999;", "→" }
+                    span {
+                        style: "flex: 1; color: #333; font-family: monospace;",
+                        "{rule.respelling}"
+                    }
+                    label {
+                        style: "font-size: 12px; color: #555; display: flex; align-items: center; gap: 4px;",
+                        input {
+                            r#type: "checkbox",
+                            checked: rule.whole_word,
+                            onchange: {
+                                let id = rule.id.clone();
+                                move |e: FormEvent| {
+                                    let mut rules = project_config().pronunciations;
+                                    if let Some(rule) = rules.iter_mut().find(|r| r.id == id) {
+                                        rule.whole_word = e.checked();
+                                    }
+                                    update_rules(rules);
+                                }
+                            },
+                        }
+                        "Whole word"
+                    }
+                    label {
+                        style: "font-size: 12px; color: #555; display: flex; align-items: center; gap: 4px;",
+                        input {
+                            r#type: "checkbox",
+                            checked: rule.case_sensitive,
+                            onchange: {
+                                let id = rule.id.clone();
+                                move |e: FormEvent| {
+                                    let mut rules = project_config().pronunciations;
+                                    if let Some(rule) = rules.iter_mut().find(|r| r.id == id) {
+                                        rule.case_sensitive = e.checked();
+                                    }
+                                    update_rules(rules);
+                                }
+                            },
+                        }
+                        "Match case"
+                    }
+                    button {
+                        style: "padding: 2px 8px; background-color: #dc3545; color: white; border: none; border-radius: 4px; cursor: pointer;",
+                        onclick: move |_| {
+                            let mut rules = project_config().pronunciations;
+                            rules.retain(|r| r.id != rule.id);
+                            update_rules(rules);
+                        },
+                        "×"
+                    }
+                }
+            }
+
+            // New rule
+            div {
+                style: "display: flex; gap: 10px; margin-top: 12px;",
+                input {
+                    value: "{new_word}",
+                    placeholder: "Word, e.g. Drizzt",
+                    oninput: move |e: FormEvent| new_word.set(e.value()),
+                    style: "flex: 1; padding: 8px; border: 1px solid #ccc; border-radius: 4px;",
+                }
+                input {
+                    value: "{new_respelling}",
+                    placeholder: "Respelling, e.g. Drizzit",
+                    oninput: move |e: FormEvent| new_respelling.set(e.value()),
+                    style: "flex: 1; padding: 8px; border: 1px solid #ccc; border-radius: 4px;",
+                }
+                button {
+                    style: "padding: 8px 15px; background-color: #28a745; color: white; border: none; border-radius: 4px; cursor: pointer;",
+                    onclick: on_add,
+                    "Add Rule"
+                }
+            }
+
+            // Preview
+            div {
+                style: "margin-top: 15px;",
+                input {
+                    value: "{preview_text}",
+                    placeholder: "Try a sentence to preview the respelling...",
+                    oninput: move |e: FormEvent| preview_text.set(e.value()),
+                    style: "width: 100%; padding: 8px; border: 1px solid #ccc; border-radius: 4px; box-sizing: border-box;",
+                }
+                if !preview_text().is_empty() {
+                    p {
+                        style: "margin: 5px 0 0 0; font-size: 13px; color: #555; font-family: monospace;",
+                        {apply_pronunciations(&preview_text(), &rules)}
+                    }
+                }
+            }
+        }
+    }
+}
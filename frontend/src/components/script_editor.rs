@@ -1,15 +1,22 @@
 use dioxus::prelude::*;
 use dioxus::document::eval; // Use eval from document module for 0.6
 use crate::models::character::Character;
-use crate::models::script::{ScriptLine, SynthesisStatus};
-use crate::services::script_parser::parse_script;
-use crate::services::api::synthesize_audio;
-use crate::utils::audio::combine_wavs;
+use crate::models::history::ScriptHistory;
+use crate::models::project::ProjectConfig;
+use crate::models::script::{group_scenes, reconcile_lines, Diagnostic, LineKind, LineOverrides, ScriptLine, Severity, SynthesisParams, SynthesisStatus, PARAMETER_RANGE};
+use crate::services::script_parser::{format_script, parse_script_with_options, referenced_variables, ParseOptions};
+use crate::services::fountain::{parse_fountain, write_fountain};
+use crate::services::subtitles::write_subtitles;
+use crate::services::tags::PARALINGUISTIC_TAGS;
+use crate::services::api::{fetch_settings, save_project_config};
+use crate::services::run_control::RunControl;
+use crate::services::synthesis_pipeline::{collect_segments, pending_lines, validate_lines, RunStatus, SynthesisPipeline};
+use crate::utils::audio::{combine_segments, AudioSegment};
 use crate::components::audio_player::AudioPlayer;
 use crate::components::progress_bar::ProgressBar;
 use crate::components::script_history::ScriptHistoryPanel;
-use std::path::PathBuf;
-use std::collections::HashMap;
+use std::ops::Range;
+use std::collections::{BTreeMap, HashMap};
 use rfd::FileDialog;
 
 #[component]
@@ -17,10 +24,13 @@ pub fn ScriptEditor(
     characters: Signal<Vec<Character>>,
     script_text: Signal<String>,
     parsed_lines: Signal<Vec<ScriptLine>>,
+    project_config: Signal<ProjectConfig>,
+    mut run_status: Signal<RunStatus>,
 ) -> Element {
-    let mut is_synthesizing = use_signal(|| false);
-    let mut synthesis_error = use_signal(|| None::<String>);
-    let mut current_line_index = use_signal(|| 0usize);
+    // Views of the current synthesis run, which lives in `run_status`
+    let is_synthesizing = use_memo(move || run_status.read().is_running());
+    let is_paused = use_memo(move || run_status.read().paused);
+    let synthesis_error = use_memo(move || run_status.read().error.clone());
     let mut export_status = use_signal(|| None::<String>);
     let mut save_history_status = use_signal(|| None::<String>);
     let mut combined_audio_path = use_signal(|| None::<String>);
@@ -28,218 +38,137 @@ pub fn ScriptEditor(
     // Synthesis configuration parameters
     let mut cfg_weight = use_signal(|| 0.5f32);
     let mut exaggeration = use_signal(|| 0.5f32);
+    let mut takes_per_line = use_signal(|| 1usize);
     let mut config_expanded = use_signal(|| false);
+    let mut new_variable_name = use_signal(String::new);
+    let mut new_variable_value = use_signal(String::new);
+    // Variable values restored with a script from history. They override the project's
+    // while that script is loaded and are only saved to the project on request.
+    let mut script_variables = use_signal(BTreeMap::<String, String>::new);
+    let parse_options = move |config: &ProjectConfig| {
+        let mut options = ParseOptions::from_config(config);
+        options.variables.extend(script_variables.read().clone());
+        options
+    };
     
-    // Parse script whenever text changes
-    let on_script_change = move |e: FormEvent| {
-        let text = e.value();
-        script_text.set(text.clone());
-        
-        let chars = characters.read();
-        let lines = parse_script(&text, &chars);
+    // The parsed script, kept in sync with text, characters and project settings;
+    // parsed once per change for both the line list and the diagnostics
+    let parse_result = use_memo(move || {
+        let options = parse_options(&project_config.read());
+        parse_script_with_options(&script_text.read(), &characters.read(), &options)
+    });
+    let diagnostics = use_memo(move || parse_result.read().diagnostics.clone());
+    use_effect(move || {
+        let lines = parse_result.read().lines.clone();
+        // Keep the audio of lines the change didn't touch
+        let lines = reconcile_lines(&parsed_lines.peek(), lines);
         parsed_lines.set(lines);
+    });
+    
+    let on_script_change = move |e: FormEvent| script_text.set(e.value());
+    
+    // Persist narrator settings; the script re-parses so unmarked lines appear or disappear
+    let mut on_config_change = move |config: ProjectConfig| {
+        project_config.set(config.clone());
+        
+        spawn(async move {
+            if let Err(e) = save_project_config(config).await {
+                println!("❌ Failed to save project config: {}", e);
+            }
+        });
     };
     
-    // Synthesize button handler
-    let on_synthesize = move |_| {
-        println!("🎬 Synthesize button clicked");
+    // Synthesizes exactly the lines with these IDs
+    let mut synthesize_lines = move |line_ids: Vec<String>| {
+        println!("🎬 Synthesizing {} line(s)", line_ids.len());
         
         // Reset state
-        synthesis_error.set(None);
-        current_line_index.set(0);
-        
-        // Validate: check for unknown characters
-        let lines = parsed_lines.read();
-        println!("📝 Parsed lines count: {}", lines.len());
+        run_status.set(RunStatus::default());
         
-        let unknown_chars: Vec<String> = lines
-            .iter()
-            .filter(|line| line.character_id.is_none())
-            .map(|line| line.character_name.clone())
-            .collect();
-        
-        if !unknown_chars.is_empty() {
+        // Refuse to start while the parser reports errors
+        let error_count = diagnostics.read().iter().filter(|d| d.severity == Severity::Error).count();
+        if error_count > 0 {
             let error_msg = format!(
-                "Unknown character(s): {}. Please add them in Character Management first.",
-                unknown_chars.join(", ")
+                "The script has {} error(s). Fix the problems listed below the script before synthesizing.",
+                error_count
             );
             println!("❌ {}", error_msg);
-            synthesis_error.set(Some(error_msg));
+            run_status.write().error = Some(error_msg);
             return;
         }
         
-        if lines.is_empty() {
-            let error_msg = "No dialogue lines to synthesize.".to_string();
+        // Validate: check for unknown characters
+        let lines = parsed_lines.read().clone();
+        println!("📝 Parsed lines count: {}", lines.len());
+        let selected: Vec<ScriptLine> = lines.iter().filter(|line| line_ids.contains(&line.id)).cloned().collect();
+        if let Err(error_msg) = validate_lines(&selected) {
             println!("❌ {}", error_msg);
-            synthesis_error.set(Some(error_msg));
+            run_status.write().error = Some(error_msg);
             return;
         }
         
-        println!("✅ Validation passed. Starting synthesis...");
-        
         // Start synthesis
-        is_synthesizing.set(true);
+        let control = RunControl::new();
+        let first_index = lines.iter().position(|line| line_ids.contains(&line.id)).unwrap_or(0);
+        run_status.set(RunStatus::started(control.clone(), first_index));
         
         // Clone data for async task
         let chars = characters.read().clone();
-        let lines_to_process: Vec<ScriptLine> = lines.clone();
+        let project = project_config.read().clone();
+        let (cfg, exag, takes) = (cfg_weight(), exaggeration(), takes_per_line());
         
-        println!("👥 Characters available: {}", chars.len());
-        for (i, line) in lines_to_process.iter().enumerate() {
-            println!("  Line {}: [{}] \"{}\"", i, line.character_name, line.text);
-        }
-        
-        spawn(async move {
+        // The run belongs to the app, not this view, so it survives switching tabs
+        spawn_forever(async move {
             println!("🚀 Async synthesis task started");
             
-            // Load settings to get output directory
-            let output_dir = match reqwest::Client::new()
-                .get("http://localhost:8000/settings")
-                .send()
-                .await
-            {
-                Ok(resp) => {
-                    if resp.status().is_success() {
-                        match resp.json::<crate::models::settings::Settings>().await {
-                            Ok(settings) => {
-                                println!("📁 Using output directory from settings: {}", settings.output_directory);
-                                std::path::PathBuf::from(settings.output_directory)
-                            }
-                            Err(e) => {
-                                let error_msg = format!("Failed to parse settings: {}", e);
-                                println!("❌ {}", error_msg);
-                                synthesis_error.set(Some(error_msg));
-                                is_synthesizing.set(false);
-                                return;
-                            }
-                        }
-                    } else {
-                        let error_msg = format!("Failed to load settings: {}", resp.status());
-                        println!("❌ {}", error_msg);
-                        synthesis_error.set(Some(error_msg));
-                        is_synthesizing.set(false);
-                        return;
-                    }
+            // Load settings to get output directory, chunk size and job limits
+            let settings = match fetch_settings().await {
+                Ok(settings) => {
+                    println!("📁 Using output directory from settings: {}", settings.output_directory);
+                    settings
                 }
                 Err(e) => {
-                    let error_msg = format!("Failed to connect to settings API: {}", e);
+                    let error_msg = format!("Failed to load settings: {}", e);
                     println!("❌ {}", error_msg);
-                    synthesis_error.set(Some(error_msg));
-                    is_synthesizing.set(false);
+                    run_status.set(RunStatus::failed(error_msg));
                     return;
                 }
             };
             
-            println!("📁 Creating output directory: {}", output_dir.display());
-            
-            if let Err(e) = std::fs::create_dir_all(&output_dir) {
-                let error_msg = format!("Failed to create output directory: {}", e);
-                println!("❌ {}", error_msg);
-                synthesis_error.set(Some(error_msg));
-                is_synthesizing.set(false);
-                return;
-            }
-            
-            println!("✅ Output directory ready");
-            
-            // Process each line sequentially
-            for (index, line) in lines_to_process.iter().enumerate() {
-                println!("\n🎯 Processing line {}/{}", index + 1, lines_to_process.len());
-                println!("   Character: {}", line.character_name);
-                println!("   Text: {}", line.text);
-                
-                current_line_index.set(index);
-                
-                // Update status to Working
-                let mut updated_lines = parsed_lines.write();
-                if let Some(l) = updated_lines.get_mut(index) {
-                    l.status = SynthesisStatus::Working;
-                }
-                drop(updated_lines);
-                
-                // Find the character to get reference audio
-                let character = chars.iter().find(|c| Some(&c.id) == line.character_id.as_ref());
-                
-                if let Some(char) = character {
-                    println!("   Found character: {}", char.name);
-                    
-                    if let Some(ref voice_path) = char.voice_path {
-                        println!("   Voice path: {}", voice_path);
-                        
-                        // Generate output filename
-                        let output_filename = format!("line_{:03}.wav", index);
-                        let output_path = output_dir.join(&output_filename);
-                        let output_path_str = output_path.to_string_lossy().to_string();
-                        
-                        println!("   Output will be: {}", output_path_str);
-                        println!("   Calling synthesis API...");
-                        
-                        // Call synthesis API
-                        match synthesize_audio(
-                            line.text.clone(),
-                            voice_path.clone(),
-                            output_path_str.clone(),
-                            cfg_weight(),
-                            exaggeration(),
-                        ).await {
-                            Ok(path) => {
-                                println!("   ✅ Synthesis successful: {}", path);
-                                
-                                // Update status to Done
-                                let mut updated_lines = parsed_lines.write();
-                                if let Some(l) = updated_lines.get_mut(index) {
-                                    l.status = SynthesisStatus::Done;
-                                    l.output_path = Some(path);
-                                }
-                            }
-                            Err(e) => {
-                                let error_msg = format!("Failed to synthesize line {}: {}", index + 1, e);
-                                println!("   ❌ {}", error_msg);
-                                
-                                // Update status to Error
-                                let mut updated_lines = parsed_lines.write();
-                                if let Some(l) = updated_lines.get_mut(index) {
-                                    l.status = SynthesisStatus::Error(e.clone());
-                                }
-                                synthesis_error.set(Some(error_msg));
-                                is_synthesizing.set(false);
-                                return;
-                            }
-                        }
-                    } else {
-                        // No voice reference
-                        let error_msg = format!("Character '{}' has no voice reference audio.", char.name);
-                        println!("   ❌ {}", error_msg);
-                        
-                        let mut updated_lines = parsed_lines.write();
-                        if let Some(l) = updated_lines.get_mut(index) {
-                            l.status = SynthesisStatus::Error("No voice reference".to_string());
-                        }
-                        synthesis_error.set(Some(error_msg));
-                        is_synthesizing.set(false);
-                        return;
-                    }
-                } else {
-                    // This shouldn't happen since we validated earlier
-                    let error_msg = format!("Character '{}' not found in character list", line.character_name);
-                    println!("   ❌ {}", error_msg);
-                    
-                    let mut updated_lines = parsed_lines.write();
-                    if let Some(l) = updated_lines.get_mut(index) {
-                        l.status = SynthesisStatus::Error("Character not found".to_string());
-                    }
-                    synthesis_error.set(Some(error_msg));
-                    is_synthesizing.set(false);
+            let pipeline = match SynthesisPipeline::for_lines(lines, &line_ids, chars, settings, project) {
+                Ok(pipeline) => pipeline.with_parameters(cfg, exag).with_takes(takes),
+                Err(error_msg) => {
+                    println!("❌ {}", error_msg);
+                    run_status.set(RunStatus::failed(error_msg));
                     return;
                 }
-            }
+            };
+            println!(
+                "✅ Validation passed. Starting synthesis of {} line(s) into {}...",
+                pipeline.pending().len(),
+                pipeline.run_dir().display()
+            );
             
-            // All done!
-            println!("\n🎉 All lines synthesized successfully!");
-            is_synthesizing.set(false);
+            let mut events = pipeline.spawn(control);
+            while let Some(event) = events.recv().await {
+                run_status.write().apply(&event, &mut parsed_lines.write());
+            }
         });
     };
     
+    // Synthesizes the lines in `range` that still need audio: the whole script, or a single scene
+    let mut synthesize_range = move |range: Range<usize>| {
+        println!("🎬 Synthesizing lines {}..{}", range.start, range.end);
+        let line_ids = pending_lines(&parsed_lines.read(), range);
+        synthesize_lines(line_ids);
+    };
+    
+    // Synthesize button handler
+    let on_synthesize = move |_| {
+        let count = parsed_lines.read().len();
+        synthesize_range(0..count);
+    };
+    
     // Export button handler
     let on_export = move |_| {
         export_status.set(None);
@@ -247,19 +176,19 @@ pub fn ScriptEditor(
         let lines = parsed_lines.read();
         
         // Check if all lines are synthesized
-        let all_done = lines.iter().all(|line| matches!(line.status, SynthesisStatus::Done));
+        let all_done = lines
+            .iter()
+            .filter(|line| line.needs_synthesis())
+            .all(|line| matches!(line.status, SynthesisStatus::Done));
         if !all_done {
             export_status.set(Some("Please synthesize the script first.".to_string()));
             return;
         }
         
-        // Collect output paths
-        let output_paths: Vec<PathBuf> = lines
-            .iter()
-            .filter_map(|line| line.output_path.as_ref().map(|p| PathBuf::from(p)))
-            .collect();
+        // Collect clips, pauses and scene chapter markers in script order
+        let segments = collect_segments(&lines);
         
-        if output_paths.is_empty() {
+        if !segments.iter().any(|s| matches!(s, AudioSegment::Clip(_))) {
             export_status.set(Some("No audio files to export.".to_string()));
             return;
         }
@@ -271,9 +200,13 @@ pub fn ScriptEditor(
         
         if let Some(output_path) = file_dialog.save_file() {
             // Perform the export
-            match combine_wavs(output_paths, output_path.clone()) {
+            match combine_segments(segments, output_path.clone()) {
                 Ok(_) => {
-                    export_status.set(Some(format!("Successfully exported to: {}", output_path.display())));
+                    // Captions timed against the exported audio, written alongside it
+                    match write_subtitles(&lines, &output_path) {
+                        Ok(_) => export_status.set(Some(format!("Successfully exported to: {} (with .srt and .vtt subtitles)", output_path.display()))),
+                        Err(e) => export_status.set(Some(format!("Exported audio to {}, but subtitles failed: {}", output_path.display(), e))),
+                    }
                     // Store the combined audio path for history saving
                     combined_audio_path.set(Some(output_path.to_string_lossy().to_string()));
                 }
@@ -284,6 +217,99 @@ pub fn ScriptEditor(
         }
     };
     
+    // Export each scene as its own numbered WAV file in a chosen folder
+    let on_export_scenes = move |_| {
+        export_status.set(None);
+        
+        let lines = parsed_lines.read();
+        
+        let all_done = lines
+            .iter()
+            .filter(|line| line.needs_synthesis())
+            .all(|line| matches!(line.status, SynthesisStatus::Done));
+        if !all_done {
+            export_status.set(Some("Please synthesize the script first.".to_string()));
+            return;
+        }
+        
+        let Some(folder) = FileDialog::new().pick_folder() else {
+            return;
+        };
+        
+        let mut exported = 0;
+        for (number, scene) in group_scenes(&lines).iter().enumerate() {
+            let segments = collect_segments(&lines[scene.lines.clone()]);
+            // Scenes without any audio, e.g. only a heading, are skipped
+            if !segments.iter().any(|s| matches!(s, AudioSegment::Clip(_))) {
+                continue;
+            }
+            
+            let title = scene.title.as_deref().unwrap_or("Opening");
+            let output_path = folder.join(format!("{:02} - {}.wav", number + 1, sanitize_file_name(title)));
+            let result = combine_segments(segments, output_path.clone())
+                .and_then(|_| write_subtitles(&lines[scene.lines.clone()], &output_path));
+            if let Err(e) = result {
+                export_status.set(Some(format!("Export of scene '{}' failed: {}", title, e)));
+                return;
+            }
+            exported += 1;
+        }
+        
+        export_status.set(Some(format!("Successfully exported {} scene(s) to: {}", exported, folder.display())));
+    };
+    
+    // Import a Fountain screenplay, replacing the current script
+    let on_import_fountain = move |_| {
+        export_status.set(None);
+        
+        let file_dialog = FileDialog::new()
+            .add_filter("Fountain Screenplay", &["fountain", "txt"]);
+        
+        if let Some(input_path) = file_dialog.pick_file() {
+            match std::fs::read_to_string(&input_path) {
+                Ok(fountain) => {
+                    let chars = characters.read();
+                    let config = project_config.read();
+                    let imported = parse_fountain(&fountain, &chars, config.narrator_character_id.as_deref());
+                    let text = format_script(&imported);
+                    
+                    script_variables.set(BTreeMap::new());
+                    script_text.set(text);
+                    export_status.set(Some(format!("Successfully imported {} lines from: {}", imported.len(), input_path.display())));
+                }
+                Err(e) => {
+                    export_status.set(Some(format!("Import failed: {}", e)));
+                }
+            }
+        }
+    };
+    
+    // Export the edited script as a Fountain screenplay
+    let on_export_fountain = move |_| {
+        export_status.set(None);
+        
+        let lines = parsed_lines.read();
+        if lines.is_empty() {
+            export_status.set(Some("Script is empty.".to_string()));
+            return;
+        }
+        
+        let file_dialog = FileDialog::new()
+            .set_file_name("script.fountain")
+            .add_filter("Fountain Screenplay", &["fountain"]);
+        
+        if let Some(output_path) = file_dialog.save_file() {
+            match std::fs::write(&output_path, write_fountain(&lines)) {
+                Ok(_) => {
+                    export_status.set(Some(format!("Successfully exported to: {}", output_path.display())));
+                }
+                Err(e) => {
+                    export_status.set(Some(format!("Export failed: {}", e)));
+                }
+            }
+        }
+    };
+    
     // Save to history handler
     let on_save_to_history = move |_| {
         save_history_status.set(None);
@@ -309,6 +335,9 @@ pub fn ScriptEditor(
             }
         }
         
+        // Record the variable values this render used, so it can be reproduced
+        let variables = referenced_variables(&script, &parse_options(&project_config.read()).variables);
+        
         let audio_path_str = audio_path.unwrap();
         let timestamp = chrono::Local::now().format("%Y-%m-%d %H:%M").to_string();
         let name = format!("Script - {}", timestamp);
@@ -319,6 +348,7 @@ pub fn ScriptEditor(
                 script,
                 audio_path_str,
                 char_mappings,
+                variables,
             ).await {
                 Ok(_) => {
                     save_history_status.set(Some("Successfully saved to history!".to_string()));
@@ -331,17 +361,38 @@ pub fn ScriptEditor(
     };
     
     // Load from history handler
-    let on_load_from_history = move |script: String| {
-        script_text.set(script.clone());
+    let on_load_from_history = move |entry: ScriptHistory| {
+        script_text.set(entry.script_text);
         
-        // Re-parse the loaded script
-        let chars = characters.read();
-        let lines = parse_script(&script, &chars);
-        parsed_lines.set(lines);
+        // Use the variable values the entry was rendered with, without touching the project's
+        let count = entry.variables.len();
+        script_variables.set(entry.variables);
         
-        save_history_status.set(Some("Script loaded from history.".to_string()));
+        if count == 0 {
+            save_history_status.set(Some("Script loaded from history.".to_string()));
+        } else {
+            save_history_status.set(Some(format!("Script loaded from history with its {} variable value(s).", count)));
+        }
     };
     let lines = parsed_lines.read();
+    let scenes = group_scenes(&lines);
+    let failed_lines: Vec<(usize, String, String, String)> = lines
+        .iter()
+        .enumerate()
+        .filter_map(|(index, line)| {
+            let reason = match (&line.status, &line.take_error) {
+                (SynthesisStatus::Error(reason), _) => reason.clone(),
+                (SynthesisStatus::Done, Some(reason)) => format!("New take failed, earlier take kept: {}", reason),
+                _ => return None,
+            };
+            Some((index, line.id.clone(), line.character_name.clone(), reason))
+        })
+        .collect();
+    let failed_ids: Vec<String> = failed_lines.iter().map(|(_, id, _, _)| id.clone()).collect();
+    let titled_scenes: Vec<(String, String)> = scenes
+        .iter()
+        .filter_map(|scene| Some((lines[scene.lines.start].id.clone(), scene.title.clone()?)))
+        .collect();
     
     rsx! {
         div {
@@ -361,7 +412,7 @@ pub fn ScriptEditor(
                         disabled: is_synthesizing(),
                         onclick: move |_| {
                             script_text.set(String::new());
-                            parsed_lines.set(Vec::new());
+                            script_variables.set(BTreeMap::new());
                         },
                         "CLEAR"
                     }
@@ -375,12 +426,53 @@ pub fn ScriptEditor(
                             "Synthesize"
                         }
                     }
+                    if let Some(control) = run_status.read().control.clone() {
+                        button {
+                            style: "background-color: #ffc107; color: #333; padding: 10px 20px; border: none; border-radius: 4px; cursor: pointer; font-size: 14px;",
+                            onclick: {
+                                let control = control.clone();
+                                move |_| {
+                                    let mut status = run_status.write();
+                                    if status.paused {
+                                        control.resume();
+                                    } else {
+                                        control.pause();
+                                    }
+                                    status.paused = !status.paused;
+                                }
+                            },
+                            if is_paused() { "Resume" } else { "Pause" }
+                        }
+                        button {
+                            style: "background-color: #6c757d; color: white; padding: 10px 20px; border: none; border-radius: 4px; cursor: pointer; font-size: 14px;",
+                            onclick: move |_| control.cancel(),
+                            "Cancel"
+                        }
+                    }
                     button {
                         style: "background-color: #28a745; color: white; padding: 10px 20px; border: none; border-radius: 4px; cursor: pointer; font-size: 14px;",
                         disabled: is_synthesizing(),
                         onclick: on_export,
                         "Export WAV"
                     }
+                    button {
+                        style: "background-color: #28a745; color: white; padding: 10px 20px; border: none; border-radius: 4px; cursor: pointer; font-size: 14px;",
+                        disabled: is_synthesizing(),
+                        onclick: on_export_scenes,
+                        "Export Scenes"
+                    }
+                    button {
+                        style: "background-color: #17a2b8; color: white; padding: 10px 20px; border: none; border-radius: 4px; cursor: pointer; font-size: 14px;",
+                        disabled: is_synthesizing(),
+                        onclick: on_import_fountain,
+                        "Import Fountain"
+                    }
+                    button {
+                        style: "background-color: #17a2b8; color: white; padding: 10px 20px; border: none; border-radius: 4px; cursor: pointer; font-size: 14px;",
+                        disabled: is_synthesizing(),
+                        onclick: on_export_fountain,
+                        "Export Fountain"
+                    }
                     button {
                         style: "background-color: #6f42c1; color: white; padding: 10px 20px; border: none; border-radius: 4px; cursor: pointer; font-size: 14px;",
                         disabled: is_synthesizing() || combined_audio_path().is_none(),
@@ -411,7 +503,7 @@ pub fn ScriptEditor(
                     if !config_expanded() {
                         span {
                             style: "font-size: 12px; color: #f57c00; font-family: monospace;",
-                            "CFG: {cfg_weight():.2} | Exag: {exaggeration():.2}"
+                            "CFG: {cfg_weight():.2} | Exag: {exaggeration():.2} | Takes: {takes_per_line()}"
                         }
                     }
                 }
@@ -437,8 +529,8 @@ pub fn ScriptEditor(
                             }
                             input {
                                 r#type: "range",
-                                min: "0",
-                                max: "1",
+                                min: "{PARAMETER_RANGE.start()}",
+                                max: "{PARAMETER_RANGE.end()}",
                                 step: "0.1",
                                 value: "{cfg_weight()}",
                                 style: "width: 100%;",
@@ -470,8 +562,8 @@ pub fn ScriptEditor(
                             }
                             input {
                                 r#type: "range",
-                                min: "0",
-                                max: "1",
+                                min: "{PARAMETER_RANGE.start()}",
+                                max: "{PARAMETER_RANGE.end()}",
                                 step: "0.1",
                                 value: "{exaggeration()}",
                                 style: "width: 100%;",
@@ -515,6 +607,34 @@ pub fn ScriptEditor(
                                 "Expressive (0.3, 0.7)"
                             }
                         }
+                        
+                        // Takes per Line
+                        div {
+                            style: "display: flex; flex-direction: column; gap: 5px;",
+                            div {
+                                style: "display: flex; justify-content: space-between; align-items: center;",
+                                label {
+                                    style: "font-size: 13px; color: #e65100; font-weight: 500;",
+                                    "Takes per Line"
+                                }
+                                input {
+                                    r#type: "number",
+                                    min: "1",
+                                    max: "10",
+                                    value: "{takes_per_line()}",
+                                    style: "width: 60px;",
+                                    oninput: move |e| {
+                                        if let Ok(val) = e.value().parse::<usize>() {
+                                            takes_per_line.set(val.clamp(1, 10));
+                                        }
+                                    },
+                                }
+                            }
+                            p {
+                                style: "margin: 0; font-size: 11px; color: #f57c00; font-style: italic;",
+                                "💡 Each run adds this many takes to a line and selects the first new one. Pick another take in the line list."
+                            }
+                        }
                     }
                 }
             }
@@ -531,6 +651,156 @@ pub fn ScriptEditor(
                     style: "margin: 5px 0 0 0; font-size: 12px; color: #1976d2;",
                     "Example: [Gandalf]: You cannot pass!"
                 }
+                p {
+                    style: "margin: 5px 0 0 0; font-size: 12px; color: #1976d2;",
+                    "Per-line settings: [Gandalf|exag=0.8,cfg=0.3]: You shall not pass!"
+                }
+                p {
+                    style: "margin: 5px 0 0 0; font-size: 12px; color: #1976d2;",
+                    "Per-line settings win over a character's synthesis defaults, which win over the sliders above."
+                }
+                p {
+                    style: "margin: 5px 0 0 0; font-size: 12px; color: #1976d2;",
+                    "Pauses on their own line: {{pause 1.5s}}, {{pause 500ms}}, {{beat}}"
+                }
+                p {
+                    style: "margin: 5px 0 0 0; font-size: 12px; color: #1976d2;",
+                    "Long speeches: indent continuation lines, or put the text under a bare [Gandalf]: line. Blank lines become short pauses."
+                }
+                p {
+                    style: "margin: 5px 0 0 0; font-size: 12px; color: #1976d2;",
+                    "Scenes: ## Scene Title on its own line. Export WAV marks each scene as a chapter; Export Scenes writes one file per scene."
+                }
+            }
+            
+            // Narrator Settings (per project)
+            div {
+                style: "background-color: #fffde7; padding: 12px; border-radius: 4px; border-left: 4px solid #fbc02d; display: flex; align-items: center; gap: 12px; flex-wrap: wrap;",
+                label {
+                    style: "display: flex; align-items: center; gap: 6px; font-size: 14px; color: #f57f17; font-weight: bold;",
+                    input {
+                        r#type: "checkbox",
+                        checked: project_config().narrate_unmarked_lines,
+                        onchange: move |e: FormEvent| {
+                            let mut config = project_config();
+                            config.narrate_unmarked_lines = e.checked();
+                            on_config_change(config);
+                        },
+                    }
+                    "Voice unmarked lines with narrator"
+                }
+                select {
+                    style: "padding: 4px; border-radius: 4px; border: 1px solid #fbc02d;",
+                    disabled: !project_config().narrate_unmarked_lines,
+                    onchange: move |e: FormEvent| {
+                        let mut config = project_config();
+                        let id = e.value();
+                        config.narrator_character_id = if id.is_empty() { None } else { Some(id) };
+                        on_config_change(config);
+                    },
+                    option { value: "", "Select narrator..." }
+                    for char in characters.read().iter() {
+                        option {
+                            value: "{char.id}",
+                            selected: project_config().narrator_character_id.as_deref() == Some(char.id.as_str()),
+                            "{char.name}"
+                        }
+                    }
+                }
+                span {
+                    style: "font-size: 12px; color: #f57f17; font-style: italic;",
+                    "Lines without [Name]: are otherwise ignored"
+                }
+            }
+            
+            // Script Variables (per project)
+            div {
+                style: "background-color: #e0f7fa; padding: 12px; border-radius: 4px; border-left: 4px solid #00acc1;",
+                p {
+                    style: "margin: 0; font-size: 14px; color: #00838f; font-weight: bold;",
+                    "Variables (use {{{{name}}}} in the script):"
+                }
+                for (name, value) in project_config().variables {
+                    div {
+                        key: "{name}",
+                        style: "display: flex; align-items: center; gap: 8px; margin-top: 6px;",
+                        span {
+                            style: "font-family: monospace; font-size: 13px; color: #006064; min-width: 140px;",
+                            "{{{{{name}}}}}"
+                        }
+                        input {
+                            value: "{value}",
+                            style: "flex-grow: 1; padding: 4px; border: 1px solid #80deea; border-radius: 4px;",
+                            onchange: {
+                                let name = name.clone();
+                                move |e: FormEvent| {
+                                    script_variables.write().remove(&name);
+                                    let mut config = project_config();
+                                    config.variables.insert(name.clone(), e.value());
+                                    on_config_change(config);
+                                }
+                            },
+                        }
+                        button {
+                            style: "padding: 2px 8px; background-color: #dc3545; color: white; border: none; border-radius: 4px; cursor: pointer;",
+                            onclick: move |_| {
+                                let mut config = project_config();
+                                config.variables.remove(&name);
+                                on_config_change(config);
+                            },
+                            "×"
+                        }
+                    }
+                }
+                if !script_variables.read().is_empty() {
+                    div {
+                        style: "display: flex; align-items: center; gap: 8px; margin-top: 8px; font-size: 12px; color: #006064;",
+                        span {
+                            style: "flex-grow: 1; font-style: italic;",
+                            "This script uses the values it was saved with: "
+                            {script_variables.read().iter().map(|(name, value)| format!("{} = {}", name, value)).collect::<Vec<_>>().join(", ")}
+                        }
+                        button {
+                            style: "background-color: #00acc1; color: white; padding: 2px 10px; border: none; border-radius: 4px; cursor: pointer; font-size: 11px;",
+                            onclick: move |_| {
+                                let mut config = project_config();
+                                config.variables.extend(script_variables.take());
+                                on_config_change(config);
+                            },
+                            "Save to Project"
+                        }
+                    }
+                }
+                div {
+                    style: "display: flex; align-items: center; gap: 8px; margin-top: 8px;",
+                    input {
+                        value: "{new_variable_name}",
+                        placeholder: "name, e.g. campaign",
+                        style: "width: 140px; padding: 4px; border: 1px solid #80deea; border-radius: 4px;",
+                        oninput: move |e: FormEvent| new_variable_name.set(e.value()),
+                    }
+                    input {
+                        value: "{new_variable_value}",
+                        placeholder: "value, e.g. Curse of Strahd",
+                        style: "flex-grow: 1; padding: 4px; border: 1px solid #80deea; border-radius: 4px;",
+                        oninput: move |e: FormEvent| new_variable_value.set(e.value()),
+                    }
+                    button {
+                        style: "background-color: #00acc1; color: white; padding: 4px 12px; border: none; border-radius: 4px; cursor: pointer; font-size: 12px;",
+                        onclick: move |_| {
+                            let name = new_variable_name().trim().trim_matches(['{', '}']).trim().to_string();
+                            if name.is_empty() {
+                                return;
+                            }
+                            let mut config = project_config();
+                            config.variables.insert(name, new_variable_value());
+                            on_config_change(config);
+                            new_variable_name.set(String::new());
+                            new_variable_value.set(String::new());
+                        },
+                        "Add Variable"
+                    }
+                }
             }
             
             // Paralinguistic Tags Guide
@@ -544,11 +814,11 @@ pub fn ScriptEditor(
                     }
                     div {
                         style: "display: flex; flex-wrap: wrap; gap: 8px; margin-top: 5px;",
-                        for tag in ["[clear throat]", "[sigh]", "[shush]", "[cough]", "[groan]", "[sniff]", "[gasp]", "[chuckle]", "[laugh]"] {
+                        for tag in PARALINGUISTIC_TAGS.map(|name| format!("[{}]", name)) {
                             button {
                                 style: "background-color: #e1bee7; color: #4a148c; padding: 2px 10px; border-radius: 12px; font-size: 11px; font-family: monospace; border: 1px solid #ce93d8; cursor: pointer; transition: background-color 0.2s;",
                                 onclick: {
-                                    let t = tag;
+                                    let t = tag.clone();
                                     move |_| {
                                         let mut eval_js = eval(
                                             r#"
@@ -628,6 +898,43 @@ pub fn ScriptEditor(
                 }
             }
             
+            // Failure Report: every line whose last synthesis failed, with its reason
+            if !is_synthesizing() && !failed_lines.is_empty() {
+                div {
+                    style: "background-color: #fff3cd; padding: 12px; border-radius: 4px; border-left: 4px solid #ffc107; color: #856404;",
+                    div {
+                        style: "display: flex; justify-content: space-between; align-items: center; margin-bottom: 8px;",
+                        strong { "Failed lines ({failed_lines.len()})" }
+                        button {
+                            style: "background-color: #ffc107; color: #333; padding: 4px 12px; border: none; border-radius: 4px; cursor: pointer; font-size: 12px;",
+                            onclick: {
+                                let failed_ids = failed_ids.clone();
+                                move |_| synthesize_lines(failed_ids.clone())
+                            },
+                            "Retry Failed Lines"
+                        }
+                    }
+                    for (index, id, name, reason) in failed_lines.iter().cloned() {
+                        div {
+                            key: "failed-{id}",
+                            style: "display: flex; align-items: center; gap: 10px; padding: 4px 0; font-size: 13px;",
+                            span {
+                                style: "cursor: pointer; text-decoration: underline;",
+                                onclick: move |_| scroll_to_line(&id),
+                                "Line {index + 1}"
+                            }
+                            span { style: "font-weight: bold;", "[{name}]" }
+                            span { style: "flex: 1;", "{reason}" }
+                            button {
+                                style: "background-color: #007bff; color: white; padding: 2px 10px; border: none; border-radius: 4px; cursor: pointer; font-size: 11px;",
+                                onclick: move |_| synthesize_range(index..index + 1),
+                                "Retry"
+                            }
+                        }
+                    }
+                }
+            }
+            
             // Export Status Display
             if let Some(status) = export_status() {
                 div {
@@ -647,21 +954,29 @@ pub fn ScriptEditor(
             // Progress Indicator
             if is_synthesizing() {
                 {
-                    let total = parsed_lines.read().len();
+                    let total = parsed_lines.read().iter().filter(|l| l.needs_synthesis()).count();
                     // Fix: If total is 0, avoid division by zero
                     if total > 0 {
                         // Current line is 0-indexed, so current working line is index + 0.5?
                         // Or just show progress as completed lines / total
                         let completed = parsed_lines.read().iter().filter(|l| matches!(l.status, SynthesisStatus::Done)).count();
-                        let current_idx = current_line_index();
+                        // Pauses are skipped, so count only the synthesizable lines before the current one
+                        let current_idx = parsed_lines.read().iter().take(run_status.read().current_index).filter(|l| l.needs_synthesis()).count();
                         
                         // We show progress based on:
                         // 1. Completed lines (solid steps)
                         // 2. The fact that one is currently processing
                         let prog = (completed as f64 / total as f64) * 100.0;
                         
+                        let working = parsed_lines.read().iter().filter(|l| l.status == SynthesisStatus::Working).count();
+                        
                         let label = if completed == total { 
                             "All lines synthesized! Combining...".to_string() 
+                        } else if is_paused() {
+                            let note = if working > 0 { ", finishing lines in progress" } else { "" };
+                            format!("Paused ({} of {} done{})", completed, total, note)
+                        } else if working > 1 {
+                            format!("Synthesizing {} lines at once ({} of {} done)...", working, completed, total)
                         } else { 
                             format!("Synthesizing line {} of {}...", current_idx + 1, total) 
                         };
@@ -691,6 +1006,49 @@ pub fn ScriptEditor(
                 }
             }
             
+            // Parser Diagnostics
+            if !diagnostics.read().is_empty() {
+                div {
+                    style: "background-color: white; border: 1px solid #ccc; border-radius: 4px; padding: 12px; display: flex; flex-direction: column; gap: 6px; max-height: 200px; overflow-y: auto;",
+                    label {
+                        style: "font-weight: bold; font-size: 14px; color: #333;",
+                        "Problems ({diagnostics.read().len()})"
+                    }
+                    for (i, diagnostic) in diagnostics.read().iter().enumerate() {
+                        {
+                            let source_line = script_text.read().lines().nth(diagnostic.line - 1).unwrap_or_default().to_string();
+                            let (before, marked, after) = split_at_span(&source_line, &diagnostic.span);
+                            let (icon, color) = match diagnostic.severity {
+                                Severity::Error => ("❌", "#dc3545"),
+                                Severity::Warning => ("⚠", "#e0a800"),
+                            };
+                            let target = diagnostic.clone();
+                            rsx! {
+                                div {
+                                    key: "{i}",
+                                    style: "cursor: pointer; padding: 4px 6px; border-left: 3px solid {color}; background-color: #f9f9f9;",
+                                    title: "Click to select in the script",
+                                    onclick: move |_| select_in_script(&target),
+                                    div {
+                                        style: "font-size: 12px; color: {color};",
+                                        "{icon} Line {diagnostic.line}, col {diagnostic.span.start + 1}: {diagnostic.message}"
+                                    }
+                                    div {
+                                        style: "font-family: 'Courier New', monospace; font-size: 12px; color: #333; white-space: pre;",
+                                        "{before}"
+                                        span {
+                                            style: "text-decoration: underline wavy {color};",
+                                            "{marked}"
+                                        }
+                                        "{after}"
+                                    }
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            
             // Live Preview Section
             if !lines.is_empty() {
                 div {
@@ -699,27 +1057,79 @@ pub fn ScriptEditor(
                         style: "font-weight: bold; font-size: 14px; color: #333;",
                         "Parsed Lines ({lines.len()})"
                     }
+                    // Scene navigation
+                    if !titled_scenes.is_empty() {
+                        div {
+                            style: "display: flex; flex-wrap: wrap; align-items: center; gap: 8px;",
+                            span {
+                                style: "font-size: 12px; color: #666;",
+                                "Jump to scene:"
+                            }
+                            for (line_id, title) in titled_scenes.iter() {
+                                button {
+                                    key: "{line_id}",
+                                    style: "background-color: #e9ecef; color: #495057; padding: 2px 10px; border-radius: 12px; font-size: 11px; border: 1px solid #ced4da; cursor: pointer;",
+                                    onclick: {
+                                        let line_id = line_id.clone();
+                                        move |_| scroll_to_line(&line_id)
+                                    },
+                                    "{title}"
+                                }
+                            }
+                        }
+                    }
                     div {
                         style: "background-color: white; border: 1px solid #ccc; border-radius: 4px; padding: 12px; max-height: 300px; overflow-y: auto;",
-                        for line in lines.iter() {
+                        for (index, line) in lines.iter().enumerate() {
                             {
+                                let scene_range = scenes
+                                    .iter()
+                                    .find(|scene| scene.title.is_some() && scene.lines.start == index)
+                                    .map(|scene| scene.lines.clone())
+                                    .unwrap_or_default();
                                 let status_color = get_status_color(&line.status);
                                 let char_color = if line.character_id.is_some() { "#28a745" } else { "#dc3545" };
-                                let is_unknown = line.character_id.is_none();
+                                let is_unknown = line.needs_synthesis() && line.character_id.is_none();
+                                let text_style = if line.kind == LineKind::Narration { "font-style: italic;" } else { "" };
                                 rsx! {
                                     div {
                                         key: "{line.id}",
+                                        id: "line-{line.id}",
                                         style: "padding: 8px; margin-bottom: 8px; border-left: 3px solid {status_color}; background-color: #f9f9f9; border-radius: 2px;",
                                         div {
                                             style: "display: flex; justify-content: space-between; align-items: center;",
-                                            div {
+                                            if let LineKind::Pause { seconds } = line.kind {
                                                 span {
-                                                    style: "font-weight: bold; color: {char_color};",
-                                                    "[{line.character_name}]"
+                                                    style: "color: #6c757d; font-style: italic;",
+                                                    "⏸ Pause {seconds}s"
                                                 }
+                                            } else if let LineKind::Scene { title } = &line.kind {
                                                 span {
-                                                    style: "margin-left: 8px; color: #333;",
-                                                    "{line.text}"
+                                                    style: "font-weight: bold; color: #495057; text-transform: uppercase; letter-spacing: 0.5px;",
+                                                    "🎬 {title}"
+                                                }
+                                                button {
+                                                    style: "background-color: #007bff; color: white; padding: 2px 10px; border: none; border-radius: 4px; cursor: pointer; font-size: 11px;",
+                                                    disabled: is_synthesizing(),
+                                                    onclick: move |_| synthesize_range(scene_range.clone()),
+                                                    "Synthesize Scene ({scene_range.len() - 1} lines)"
+                                                }
+                                            } else {
+                                                div {
+                                                    span {
+                                                        style: "font-weight: bold; color: {char_color};",
+                                                        "[{line.character_name}]"
+                                                    }
+                                                    span {
+                                                        style: "margin-left: 8px; color: #333; white-space: pre-wrap; {text_style}",
+                                                        "{line.text}"
+                                                    }
+                                                    if !line.overrides.is_empty() {
+                                                        span {
+                                                            style: "margin-left: 8px; font-size: 11px; color: #e65100; font-family: monospace;",
+                                                            {format_overrides(&line.overrides)}
+                                                        }
+                                                    }
                                                 }
                                             }
                                              if is_unknown {
@@ -728,9 +1138,57 @@ pub fn ScriptEditor(
                                                      "⚠ Unknown character"
                                                  }
                                              } else if matches!(line.status, SynthesisStatus::Done) {
-                                                 span {
-                                                     style: "font-size: 12px; color: #28a745;",
-                                                     "{get_status_icon(&line.status)} Done"
+                                                 div {
+                                                     style: "display: flex; align-items: center; gap: 8px;",
+                                                     span {
+                                                         style: "font-size: 12px; color: #28a745;",
+                                                         "{get_status_icon(&line.status)} Done"
+                                                     }
+                                                     if let Some(reason) = &line.take_error {
+                                                         span {
+                                                             style: "font-size: 12px; color: #dc3545;",
+                                                             title: "{reason}",
+                                                             "⚠ New take failed"
+                                                         }
+                                                     }
+                                                     if let Some(output_path) = line.output_path.clone() {
+                                                         button {
+                                                             style: "background-color: #28a745; color: white; padding: 2px 10px; border: none; border-radius: 4px; cursor: pointer; font-size: 11px;",
+                                                             title: "Play this line",
+                                                             onclick: move |_| play_audio(&output_path),
+                                                             "▶ Play"
+                                                         }
+                                                     }
+                                                     button {
+                                                         style: "background-color: #007bff; color: white; padding: 2px 10px; border: none; border-radius: 4px; cursor: pointer; font-size: 11px;",
+                                                         title: "Synthesize a new take of this line with the current settings",
+                                                         disabled: is_synthesizing(),
+                                                         onclick: move |_| synthesize_range(index..index + 1),
+                                                         "⟳ Regenerate"
+                                                     }
+                                                 }
+                                             } else if line.status == SynthesisStatus::Idle && line.needs_synthesis() {
+                                                 button {
+                                                     style: "background-color: #007bff; color: white; padding: 2px 10px; border: none; border-radius: 4px; cursor: pointer; font-size: 11px;",
+                                                     title: "Synthesize just this line with the current settings",
+                                                     disabled: is_synthesizing(),
+                                                     onclick: move |_| synthesize_range(index..index + 1),
+                                                     "⟳ Generate"
+                                                 }
+                                             } else if let SynthesisStatus::Error(reason) = &line.status {
+                                                 div {
+                                                     style: "display: flex; align-items: center; gap: 8px;",
+                                                     span {
+                                                         style: "font-size: 12px; color: #dc3545;",
+                                                         title: "{reason}",
+                                                         "{get_status_icon(&line.status)} Failed"
+                                                     }
+                                                     button {
+                                                         style: "background-color: #007bff; color: white; padding: 2px 10px; border: none; border-radius: 4px; cursor: pointer; font-size: 11px;",
+                                                         disabled: is_synthesizing(),
+                                                         onclick: move |_| synthesize_range(index..index + 1),
+                                                         "Retry"
+                                                     }
                                                  }
                                              }
                                          }
@@ -740,7 +1198,53 @@ pub fn ScriptEditor(
                                                  div {
                                                      style: "margin-top: 8px;",
                                                      AudioPlayer {
-                                                         audio_url: format!("http://localhost:8000/files/audio/{}", urlencoding::encode(output_path))
+                                                         audio_url: audio_url(output_path)
+                                                     }
+                                                 }
+                                             }
+                                         }
+                                         // Audition and pick between takes
+                                         if line.takes.len() > 1 {
+                                             div {
+                                                 style: "margin-top: 8px; display: flex; flex-direction: column; gap: 6px;",
+                                                 for (take_index, take) in line.takes.iter().enumerate() {
+                                                     {
+                                                         let is_selected = line.selected_take() == Some(take_index);
+                                                         let background = if is_selected { "#e8f5e9" } else { "transparent" };
+                                                         rsx! {
+                                                             div {
+                                                                 key: "{take.path}",
+                                                                 style: "display: flex; align-items: center; gap: 10px; padding: 4px; border-radius: 4px; background-color: {background};",
+                                                                 span {
+                                                                     style: "font-size: 12px; font-weight: bold; color: #495057; min-width: 50px;",
+                                                                     "Take {take_index + 1}"
+                                                                 }
+                                                                 AudioPlayer {
+                                                                     audio_url: audio_url(&take.path)
+                                                                 }
+                                                                 span {
+                                                                     style: "font-size: 11px; color: #6c757d; font-family: monospace;",
+                                                                     {format_take_params(&take.params)}
+                                                                 }
+                                                                 if is_selected {
+                                                                     span {
+                                                                         style: "font-size: 12px; color: #28a745;",
+                                                                         "✓ Selected"
+                                                                     }
+                                                                 } else {
+                                                                     button {
+                                                                         style: "background-color: #28a745; color: white; padding: 2px 10px; border: none; border-radius: 4px; cursor: pointer; font-size: 11px;",
+                                                                         disabled: is_synthesizing(),
+                                                                         onclick: move |_| {
+                                                                             if let Some(line) = parsed_lines.write().get_mut(index) {
+                                                                                 line.select_take(take_index);
+                                                                             }
+                                                                         },
+                                                                         "Select"
+                                                                     }
+                                                                 }
+                                                             }
+                                                         }
                                                      }
                                                  }
                                              }
@@ -761,6 +1265,15 @@ pub fn ScriptEditor(
     }
 }
 
+/// Replaces characters that aren't allowed in file names on common platforms.
+fn sanitize_file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| if matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|') { '_' } else { c })
+        .collect::<String>()
+        .trim()
+        .to_string()
+}
+
 fn get_status_color(status: &SynthesisStatus) -> &'static str {
     match status {
         SynthesisStatus::Idle => "#6c757d",
@@ -780,3 +1293,76 @@ fn get_status_icon(status: &SynthesisStatus) -> &'static str {
         SynthesisStatus::Error(_) => "❌",
     }
 }
+
+/// URL the backend serves a local audio file under.
+fn audio_url(path: &str) -> String {
+    format!("http://localhost:8000/files/audio/{}", urlencoding::encode(path))
+}
+
+fn format_take_params(params: &SynthesisParams) -> String {
+    let mut parts = vec![
+        format!("cfg {:.2}", params.cfg_weight),
+        format!("exag {:.2}", params.exaggeration),
+        params.model.clone(),
+    ];
+    if let Some(seed) = params.seed {
+        parts.push(format!("seed {}", seed));
+    }
+    parts.join(" · ")
+}
+
+fn format_overrides(overrides: &LineOverrides) -> String {
+    let mut parts = Vec::new();
+    if let Some(cfg) = overrides.cfg_weight {
+        parts.push(format!("cfg {:.2}", cfg));
+    }
+    if let Some(exag) = overrides.exaggeration {
+        parts.push(format!("exag {:.2}", exag));
+    }
+    parts.join(" · ")
+}
+
+/// Splits a line into the text before, inside and after a character-column span.
+fn split_at_span(line: &str, span: &std::ops::Range<usize>) -> (String, String, String) {
+    let chars: Vec<char> = line.chars().collect();
+    let start = span.start.min(chars.len());
+    let end = span.end.clamp(start, chars.len());
+    (
+        chars[..start].iter().collect(),
+        chars[start..end].iter().collect(),
+        chars[end..].iter().collect(),
+    )
+}
+
+/// Plays a clip through one shared player, so starting a line stops the last one.
+fn play_audio(path: &str) {
+    let _ = eval(&format!(
+        "window.voiceboxLinePlayer ??= new Audio(); window.voiceboxLinePlayer.src = '{}'; window.voiceboxLinePlayer.play();",
+        audio_url(path)
+    ));
+}
+
+/// Scrolls the parsed line list to the given line.
+fn scroll_to_line(line_id: &str) {
+    let _ = eval(&format!(
+        "document.getElementById('line-{}')?.scrollIntoView({{ behavior: 'smooth', block: 'start' }});",
+        line_id
+    ));
+}
+
+/// Selects a diagnostic's span in the script textarea.
+fn select_in_script(diagnostic: &Diagnostic) {
+    let eval_js = eval(
+        r#"
+        (async () => {
+            let [line, start, end] = await dioxus.recv();
+            let textarea = document.getElementById('script-textarea');
+            let lines = textarea.value.split('\n');
+            let offset = lines.slice(0, line - 1).reduce((sum, l) => sum + l.length + 1, 0);
+            textarea.focus();
+            textarea.setSelectionRange(offset + start, offset + end);
+        })();
+        "#
+    );
+    let _ = eval_js.send(serde_json::json!([diagnostic.line, diagnostic.span.start, diagnostic.span.end]));
+}
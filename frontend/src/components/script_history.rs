@@ -5,7 +5,7 @@ use crate::components::audio_player::AudioPlayer;
 use chrono::Local;
 
 #[component]
-pub fn ScriptHistoryPanel(on_load_script: EventHandler<String>) -> Element {
+pub fn ScriptHistoryPanel(on_load_script: EventHandler<ScriptHistory>) -> Element {
     let mut history = use_signal(|| Vec::<ScriptHistory>::new());
     let mut is_loading = use_signal(|| false);
     let mut error_msg = use_signal(|| None::<String>);
@@ -124,9 +124,9 @@ pub fn ScriptHistoryPanel(on_load_script: EventHandler<String>) -> Element {
                                             button {
                                                 style: "padding: 5px 10px; background-color: #17a2b8; color: white; border: none; border-radius: 4px; cursor: pointer; font-size: 0.85em;",
                                                 onclick: {
-                                                    let script = entry.script_text.clone();
+                                                    let entry = entry.clone();
                                                     move |_| {
-                                                        on_load_script.call(script.clone());
+                                                        on_load_script.call(entry.clone());
                                                     }
                                                 },
                                                 "Load"
@@ -201,7 +201,7 @@ async fn load_history(
     is_loading.set(true);
     error_msg.set(None);
     
-    match fetch_history().await {
+    match fetch_history(None).await {
         Ok(data) => {
             history.set(data);
         }
@@ -1,12 +1,25 @@
 use dioxus::prelude::*;
 use crate::models::settings::Settings;
+use crate::models::project::ProjectConfig;
+use crate::components::pronunciation_dictionary::PronunciationDictionary;
+use crate::services::api::save_project_config;
+use crate::services::synthesis_cache::SynthesisCache;
+use std::path::Path;
 
 #[component]
-pub fn SettingsPanel() -> Element {
+pub fn SettingsPanel(mut project_config: Signal<ProjectConfig>) -> Element {
     let mut settings = use_signal(|| Settings::default());
     let mut is_loading = use_signal(|| false);
     let mut save_status = use_signal(|| None::<String>);
     let mut error_msg = use_signal(|| None::<String>);
+    // Bumped after clearing the cache so its usage is measured again
+    let mut cache_generation = use_signal(|| 0u32);
+    
+    let cache_usage = use_memo(move || {
+        cache_generation();
+        let s = settings.read();
+        SynthesisCache::in_output_dir(Path::new(&s.output_directory), s.cache_limit_mb).usage()
+    });
     
     // Load settings on mount
     use_effect(move || {
@@ -103,7 +116,7 @@ pub fn SettingsPanel() -> Element {
                     }
                     p {
                         style: "margin: 5px 0 0 0; font-size: 12px; color: #666;",
-                        "Where synthesized audio files will be saved"
+                        "Where synthesized audio files will be saved. Each run gets its own folder, under one per project, with a manifest.json describing its clips."
                     }
                 }
                 
@@ -206,6 +219,205 @@ pub fn SettingsPanel() -> Element {
                 }
             }
             
+            // Synthesis Settings
+            div {
+                style: "background-color: white; padding: 20px; border-radius: 8px; box-shadow: 0 2px 4px rgba(0,0,0,0.1);",
+                h3 {
+                    style: "margin-top: 0; color: #333;",
+                    "Synthesis Settings"
+                }
+                
+                // Chunk Size
+                div {
+                    style: "margin-bottom: 20px;",
+                    label {
+                        style: "display: block; font-weight: bold; margin-bottom: 8px; color: #555;",
+                        "Maximum Characters per Request"
+                    }
+                    input {
+                        r#type: "number",
+                        min: "0",
+                        step: "50",
+                        value: "{settings().max_chunk_chars}",
+                        oninput: move |e: FormEvent| {
+                            if let Ok(val) = e.value().parse::<usize>() {
+                                let mut s = settings();
+                                s.max_chunk_chars = val;
+                                settings.set(s);
+                                save_status.set(None);
+                            }
+                        },
+                        style: "width: 120px; padding: 8px; border: 1px solid #ccc; border-radius: 4px;",
+                    }
+                    p {
+                        style: "margin: 5px 0 0 0; font-size: 12px; color: #666;",
+                        "Longer lines are split at sentence and clause boundaries, synthesized in pieces and joined back into one clip. Set to 0 to send every line whole."
+                    }
+                }
+                
+                // Parallel Jobs
+                div {
+                    style: "margin-bottom: 20px;",
+                    label {
+                        style: "display: block; font-weight: bold; margin-bottom: 8px; color: #555;",
+                        "Concurrent Synthesis Jobs"
+                    }
+                    input {
+                        r#type: "number",
+                        min: "1",
+                        max: "16",
+                        value: "{settings().max_concurrent_jobs}",
+                        oninput: move |e: FormEvent| {
+                            if let Ok(val) = e.value().parse::<usize>() {
+                                let mut s = settings();
+                                s.max_concurrent_jobs = val.clamp(1, 16);
+                                settings.set(s);
+                                save_status.set(None);
+                            }
+                        },
+                        style: "width: 120px; padding: 8px; border: 1px solid #ccc; border-radius: 4px;",
+                    }
+                    p {
+                        style: "margin: 5px 0 0 0; font-size: 12px; color: #666;",
+                        "How many lines are sent to the backend at once. Keep at 1 unless the backend can synthesize requests in parallel."
+                    }
+                }
+                
+                // Failure Handling
+                div {
+                    style: "margin-bottom: 20px;",
+                    label {
+                        style: "display: block; font-weight: bold; margin-bottom: 8px; color: #555;",
+                        "Retries per Request"
+                    }
+                    input {
+                        r#type: "number",
+                        min: "0",
+                        max: "10",
+                        value: "{settings().max_retries}",
+                        oninput: move |e: FormEvent| {
+                            if let Ok(val) = e.value().parse::<u32>() {
+                                let mut s = settings();
+                                s.max_retries = val.min(10);
+                                settings.set(s);
+                                save_status.set(None);
+                            }
+                        },
+                        style: "width: 120px; padding: 8px; border: 1px solid #ccc; border-radius: 4px;",
+                    }
+                    p {
+                        style: "margin: 5px 0 10px 0; font-size: 12px; color: #666;",
+                        "Requests that can't reach the backend or hit a server error are retried, waiting 1s, 2s, 4s, ... in between."
+                    }
+                    label {
+                        style: "display: flex; align-items: center; gap: 8px; font-weight: bold; color: #555;",
+                        input {
+                            r#type: "checkbox",
+                            checked: settings().continue_on_error,
+                            onchange: move |e: FormEvent| {
+                                let mut s = settings();
+                                s.continue_on_error = e.checked();
+                                settings.set(s);
+                                save_status.set(None);
+                            },
+                        }
+                        "Continue past failed lines"
+                    }
+                    p {
+                        style: "margin: 5px 0 0 0; font-size: 12px; color: #666;",
+                        "Keep synthesizing when a line still fails after its retries. Failed lines are listed at the end, each with a retry button."
+                    }
+                }
+                
+                // Synthesis Cache
+                div {
+                    label {
+                        style: "display: block; font-weight: bold; margin-bottom: 8px; color: #555;",
+                        "Synthesis Cache Limit (MB)"
+                    }
+                    div {
+                        style: "display: flex; gap: 10px; align-items: center;",
+                        input {
+                            r#type: "number",
+                            min: "0",
+                            step: "100",
+                            value: "{settings().cache_limit_mb}",
+                            oninput: move |e: FormEvent| {
+                                if let Ok(val) = e.value().parse::<u64>() {
+                                    let mut s = settings();
+                                    s.cache_limit_mb = val;
+                                    settings.set(s);
+                                    save_status.set(None);
+                                }
+                            },
+                            style: "width: 120px; padding: 8px; border: 1px solid #ccc; border-radius: 4px;",
+                        }
+                        span {
+                            style: "font-size: 13px; color: #555;",
+                            {
+                                let (count, bytes) = cache_usage();
+                                format!("{} clip(s), {:.1} MB in use", count, bytes as f64 / (1024.0 * 1024.0))
+                            }
+                        }
+                        button {
+                            style: "padding: 8px 15px; background-color: #dc3545; color: white; border: none; border-radius: 4px; cursor: pointer;",
+                            disabled: cache_usage().0 == 0,
+                            onclick: move |_| {
+                                let s = settings();
+                                let cache = SynthesisCache::in_output_dir(Path::new(&s.output_directory), s.cache_limit_mb);
+                                match cache.clear() {
+                                    Ok(_) => {
+                                        save_status.set(Some("Synthesis cache cleared.".to_string()));
+                                        error_msg.set(None);
+                                    }
+                                    Err(e) => error_msg.set(Some(format!("Failed to clear cache: {}", e))),
+                                }
+                                cache_generation += 1;
+                            },
+                            "Clear Cache"
+                        }
+                    }
+                    p {
+                        style: "margin: 5px 0 0 0; font-size: 12px; color: #666;",
+                        "Clips are reused when the text, voice reference and parameters match an earlier run. Stored in a .cache folder inside the output directory; the least recently used clips are removed beyond the limit."
+                    }
+                }
+            }
+            
+            // Text Normalization (per project)
+            div {
+                style: "background-color: white; padding: 20px; border-radius: 8px; box-shadow: 0 2px 4px rgba(0,0,0,0.1);",
+                h3 {
+                    style: "margin-top: 0; color: #333;",
+                    "Text Normalization"
+                }
+                label {
+                    style: "display: flex; align-items: center; gap: 8px; font-weight: bold; color: #555;",
+                    input {
+                        r#type: "checkbox",
+                        checked: project_config().normalize_text,
+                        onchange: move |e: FormEvent| {
+                            let mut config = project_config();
+                            config.normalize_text = e.checked();
+                            project_config.set(config.clone());
+                            spawn(async move {
+                                if let Err(e) = save_project_config(config).await {
+                                    error_msg.set(Some(format!("Failed to save project config: {}", e)));
+                                }
+                            });
+                        },
+                    }
+                    "Spell out dice, coins, numbers and RPG abbreviations"
+                }
+                p {
+                    style: "margin: 5px 0 0 0; font-size: 12px; color: #666;",
+                    "Reads \"3d6\" as \"three d six\", \"50 gp\" as \"fifty gold pieces\" and \"AC 15\" as \"armor class fifteen\". Applies to the active project; the script text is not changed."
+                }
+            }
+            
+            // Pronunciation Dictionary (per project)
+            PronunciationDictionary { project_config: project_config }
+            
             // File Naming Settings (placeholder for future implementation)
             div {
                 style: "background-color: white; padding: 20px; border-radius: 8px; box-shadow: 0 2px 4px rgba(0,0,0,0.1);",
@@ -1,14 +1,16 @@
 use dioxus::prelude::*;
 
-mod models;
-mod services;
-mod components;
-mod utils;
+pub mod models;
+pub mod services;
+pub mod components;
+pub mod utils;
 
 use models::character::Character;
-use services::api::{check_backend_health, fetch_characters, backend_save_characters};
+use models::project::ProjectConfig;
+use services::api::{check_backend_health, fetch_characters, backend_save_characters, fetch_project_config};
 use components::{sidebar::Sidebar, editor::Editor, script_editor::ScriptEditor, settings_panel::SettingsPanel, project_selector::ProjectSelector, audio_post_processing::AudioPostProcessing};
 use models::script::ScriptLine;
+use services::synthesis_pipeline::RunStatus;
 
 #[derive(Clone, Copy, PartialEq)]
 enum Tab {
@@ -41,14 +43,20 @@ fn app() -> Element {
     let mut refresh_trigger = use_signal(|| 0);
     let script_text = use_signal(|| String::new()); // Removed 'mut'
     let parsed_lines = use_signal(|| Vec::<ScriptLine>::new()); // Removed 'mut'
+    // Kept here so a synthesis run outlives the Script Editor tab
+    let run_status = use_signal(RunStatus::default);
+    let mut project_config = use_signal(ProjectConfig::default);
 
     // Fetch characters on startup and when project changes
     use_effect(move || {
         let _ = refresh_trigger();
         spawn(async move {
-            if let Ok(chars) = fetch_characters().await {
+            if let Ok(chars) = fetch_characters(None).await {
                 characters.set(chars);
             }
+            if let Ok(config) = fetch_project_config(None).await {
+                project_config.set(config);
+            }
         });
     });
 
@@ -206,6 +214,8 @@ fn app() -> Element {
                         characters: characters,
                         script_text: script_text,
                         parsed_lines: parsed_lines,
+                        project_config: project_config,
+                        run_status: run_status,
                     }
                 }
                 
@@ -214,7 +224,7 @@ fn app() -> Element {
                 }
                 
                 if is_settings_tab {
-                    SettingsPanel {}
+                    SettingsPanel { project_config: project_config }
                 }
             }
         }
@@ -7,6 +7,29 @@ pub struct Character {
     pub name: String,
     pub description: String,
     pub voice_path: Option<String>,
+    /// Alternative names accepted in script cues, e.g. `Gandalf the Grey` or `GTG`.
+    #[serde(default)]
+    pub aliases: Vec<String>,
+    #[serde(default)]
+    pub synthesis_defaults: VoiceDefaults,
+}
+
+/// Synthesis parameters for this character's lines, used unless a line sets its own.
+/// Unset values fall back to the global sliders and the backend's default model.
+#[derive(Serialize, Deserialize, Clone, PartialEq, Debug, Default)]
+pub struct VoiceDefaults {
+    pub cfg_weight: Option<f32>,
+    pub exaggeration: Option<f32>,
+    /// One of `SYNTHESIS_MODELS`.
+    pub model: Option<String>,
+    /// Fixed sampling seed, so a line comes out the same on every run.
+    pub seed: Option<u64>,
+}
+
+impl VoiceDefaults {
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
 }
 
 impl Character {
@@ -16,6 +39,8 @@ impl Character {
             name,
             description: String::new(),
             voice_path: None,
+            aliases: Vec::new(),
+            synthesis_defaults: VoiceDefaults::default(),
         }
     }
 }
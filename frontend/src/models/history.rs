@@ -1,6 +1,6 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ScriptHistory {
@@ -10,4 +10,7 @@ pub struct ScriptHistory {
     pub audio_path: String,
     pub created_at: DateTime<Utc>,
     pub character_mappings: HashMap<String, String>,
+    /// `{{name}}` values the script was rendered with.
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
 }
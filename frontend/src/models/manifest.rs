@@ -0,0 +1,48 @@
+use serde::{Deserialize, Serialize};
+use chrono::{DateTime, Utc};
+
+/// Name of the manifest written into each run's output directory.
+pub const MANIFEST_FILE_NAME: &str = "manifest.json";
+
+/// Record of one synthesis run: what each line was rendered from and with, so a
+/// clip can be traced back or reproduced after the script has moved on.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct RenderManifest {
+    pub project_id: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    /// How the run ended, e.g. `completed` or `cancelled`.
+    pub outcome: String,
+    /// Lines the run attempted, in script order.
+    pub lines: Vec<ManifestLine>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManifestLine {
+    /// Position of the line in the script, 0-based.
+    pub index: usize,
+    pub line_id: String,
+    pub character_id: Option<String>,
+    pub character_name: String,
+    pub text: String,
+    pub voice_reference: Option<String>,
+    /// SHA-256 of the voice reference when the run read it.
+    pub voice_reference_sha256: Option<String>,
+    pub takes: Vec<ManifestTake>,
+    /// Why the line has no audio, if it failed.
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ManifestTake {
+    /// Clip file name, relative to the run directory.
+    pub file: String,
+    pub cfg_weight: f32,
+    pub exaggeration: f32,
+    pub model: String,
+    pub seed: Option<u64>,
+    pub duration_seconds: f32,
+    pub started_at: DateTime<Utc>,
+    /// Wall-clock time the take took to synthesize, including retries and cache hits.
+    pub synthesis_seconds: f32,
+}
@@ -4,3 +4,4 @@ pub mod settings;
 pub mod project;
 pub mod history;
 pub mod audio_processing;
+pub mod manifest;
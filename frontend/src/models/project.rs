@@ -1,5 +1,7 @@
 use serde::{Deserialize, Serialize};
 use chrono::{DateTime, Utc};
+use std::collections::BTreeMap;
+use uuid::Uuid;
 
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct Project {
@@ -8,3 +10,59 @@ pub struct Project {
     pub base_path: String,
     pub created_at: DateTime<Utc>,
 }
+
+/// Per-project script and synthesis preferences, stored next to the project's characters.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Default)]
+pub struct ProjectConfig {
+    /// Character that voices prose lines outside the `[Name]: text` format.
+    #[serde(default)]
+    pub narrator_character_id: Option<String>,
+    /// When false, unmarked lines are ignored even if a narrator is bound.
+    #[serde(default)]
+    pub narrate_unmarked_lines: bool,
+    /// Respellings applied to line text just before it is sent for synthesis.
+    #[serde(default)]
+    pub pronunciations: Vec<PronunciationRule>,
+    /// Spell out dice, coins, numbers and tabletop abbreviations before synthesis.
+    #[serde(default)]
+    pub normalize_text: bool,
+    /// Values for `{{name}}` placeholders in scripts, e.g. `campaign` → "Curse of Strahd".
+    #[serde(default)]
+    pub variables: BTreeMap<String, String>,
+}
+
+/// Replaces a word the model mispronounces with a respelling it reads correctly,
+/// e.g. `Drizzt` → `Drizzit`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct PronunciationRule {
+    /// Identifies the rule while its word and flags are edited.
+    #[serde(default = "new_rule_id")]
+    pub id: String,
+    pub word: String,
+    pub respelling: String,
+    /// Only match the word on its own, not inside longer words.
+    #[serde(default = "default_true")]
+    pub whole_word: bool,
+    #[serde(default)]
+    pub case_sensitive: bool,
+}
+
+impl PronunciationRule {
+    pub fn new(word: String, respelling: String) -> Self {
+        Self {
+            id: new_rule_id(),
+            word,
+            respelling,
+            whole_word: true,
+            case_sensitive: false,
+        }
+    }
+}
+
+fn new_rule_id() -> String {
+    Uuid::new_v4().to_string()
+}
+
+fn default_true() -> bool {
+    true
+}
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use std::ops::{Range, RangeInclusive};
 use uuid::Uuid;
 
 #[derive(Clone, PartialEq, Debug)]
@@ -10,14 +11,86 @@ pub enum SynthesisStatus {
     Error(String),
 }
 
+/// Separator between paragraphs within one line's text.
+pub const PARAGRAPH_SEPARATOR: &str = "\n\n";
+
+/// Silence inserted between paragraphs of one line when it is synthesized.
+pub const PARAGRAPH_PAUSE_SECONDS: f32 = 0.6;
+
+/// Where a script line came from in the source text.
+#[derive(Clone, PartialEq, Debug)]
+pub enum LineKind {
+    /// A `[Name]: text` line.
+    Dialogue,
+    /// Unmarked prose voiced by the project's narrator.
+    Narration,
+    /// A `{pause 1.5s}` or `{beat}` directive, rendered as silence on export.
+    Pause { seconds: f32 },
+    /// A `## Scene Title` heading that marks where a new scene begins.
+    Scene { title: String },
+}
+
+/// Values the CFG weight and exaggeration sliders allow, and inline overrides must stay within.
+pub const PARAMETER_RANGE: RangeInclusive<f32> = 0.0..=1.0;
+
+/// Synthesis parameters written inline in a cue, e.g. `[Gandalf|exag=0.8,cfg=0.3]:`.
+/// Unset values fall back to the global sliders.
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct LineOverrides {
+    pub cfg_weight: Option<f32>,
+    pub exaggeration: Option<f32>,
+}
+
+impl LineOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.cfg_weight.is_none() && self.exaggeration.is_none()
+    }
+}
+
+/// Parameters of one synthesis request, after line overrides and character
+/// defaults are resolved.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SynthesisParams {
+    pub cfg_weight: f32,
+    pub exaggeration: f32,
+    pub model: String,
+    /// Sampling seed; random when unset.
+    pub seed: Option<u64>,
+}
+
+impl SynthesisParams {
+    /// The parameters for take `take` of a line. A fixed seed is advanced per take,
+    /// so takes differ but each can be reproduced.
+    pub fn for_take(&self, take: usize) -> Self {
+        Self {
+            seed: self.seed.map(|seed| seed.wrapping_add(take as u64)),
+            ..self.clone()
+        }
+    }
+}
+
+/// One synthesized rendition of a line, kept with the parameters that made it.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Take {
+    pub path: String,
+    pub params: SynthesisParams,
+}
+
 #[derive(Clone, PartialEq, Debug)]
 pub struct ScriptLine {
     pub id: String,
     pub character_id: Option<String>,
     pub character_name: String,
     pub text: String,
+    pub kind: LineKind,
+    pub overrides: LineOverrides,
     pub status: SynthesisStatus,
+    /// Audio of the selected take, used for playback and export.
     pub output_path: Option<String>,
+    /// Every take synthesized for this line, oldest first.
+    pub takes: Vec<Take>,
+    /// Why the last attempt at a new take failed, while the line kept its earlier take.
+    pub take_error: Option<String>,
 }
 
 impl ScriptLine {
@@ -27,8 +100,269 @@ impl ScriptLine {
             character_id,
             character_name,
             text,
+            kind: LineKind::Dialogue,
+            overrides: LineOverrides::default(),
             status: SynthesisStatus::Idle,
             output_path: None,
+            takes: Vec::new(),
+            take_error: None,
+        }
+    }
+
+    /// Creates a line of unmarked prose spoken by the narrator.
+    pub fn narration(character_name: String, text: String, character_id: Option<String>) -> Self {
+        Self {
+            kind: LineKind::Narration,
+            ..Self::new(character_name, text, character_id)
+        }
+    }
+
+    /// Creates a silent gap of the given length.
+    pub fn pause(seconds: f32) -> Self {
+        Self {
+            kind: LineKind::Pause { seconds },
+            ..Self::new(String::new(), String::new(), None)
+        }
+    }
+
+    /// Creates a scene marker with the given title.
+    pub fn scene(title: String) -> Self {
+        Self {
+            kind: LineKind::Scene { title },
+            ..Self::new(String::new(), String::new(), None)
+        }
+    }
+
+    /// The line's paragraphs, synthesized separately and joined with a short pause.
+    pub fn paragraphs(&self) -> Vec<&str> {
+        self.text
+            .split(PARAGRAPH_SEPARATOR)
+            .map(str::trim)
+            .filter(|p| !p.is_empty())
+            .collect()
+    }
+
+    /// Whether two lines would be synthesized identically, ignoring identity and status.
+    pub fn same_content(&self, other: &ScriptLine) -> bool {
+        self.character_id == other.character_id
+            && self.character_name == other.character_name
+            && self.text == other.text
+            && self.kind == other.kind
+            && self.overrides == other.overrides
+    }
+
+    /// Index of the take whose audio is the line's output.
+    pub fn selected_take(&self) -> Option<usize> {
+        self.takes.iter().position(|take| Some(&take.path) == self.output_path.as_ref())
+    }
+
+    /// Makes take `index` the line's output. Out-of-range indices are ignored.
+    pub fn select_take(&mut self, index: usize) {
+        if let Some(take) = self.takes.get(index) {
+            self.output_path = Some(take.path.clone());
+            self.status = SynthesisStatus::Done;
         }
     }
+
+    /// Whether this line is sent to the synthesis backend (pauses and scene markers are not).
+    pub fn needs_synthesis(&self) -> bool {
+        !matches!(self.kind, LineKind::Pause { .. } | LineKind::Scene { .. })
+    }
+}
+
+/// A run of script lines under one `## Scene Title` heading.
+#[derive(Clone, PartialEq, Debug)]
+pub struct SceneGroup {
+    /// The heading's title, or `None` for lines before the first heading.
+    pub title: Option<String>,
+    /// Indices of the scene's lines, including its heading.
+    pub lines: Range<usize>,
+}
+
+/// Splits lines into scenes at each scene heading. Lines before the first heading
+/// form an untitled scene, so a script without headings is one untitled scene.
+pub fn group_scenes(lines: &[ScriptLine]) -> Vec<SceneGroup> {
+    let mut scenes: Vec<SceneGroup> = Vec::new();
+    
+    for (index, line) in lines.iter().enumerate() {
+        match &line.kind {
+            LineKind::Scene { title } => scenes.push(SceneGroup {
+                title: Some(title.clone()),
+                lines: index..index + 1,
+            }),
+            _ => match scenes.last_mut() {
+                Some(scene) => scene.lines.end = index + 1,
+                None => scenes.push(SceneGroup { title: None, lines: index..index + 1 }),
+            },
+        }
+    }
+    
+    scenes
+}
+
+/// Carries line identity over from the previous parse of the same script.
+///
+/// Lines are matched with a longest common subsequence on their content (speaker,
+/// text, kind and overrides). Matched lines keep their previous ID, status, output
+/// and takes; edited and inserted lines stay fresh and `Idle`, so only they need to be
+/// synthesized again.
+pub fn reconcile_lines(previous: &[ScriptLine], mut lines: Vec<ScriptLine>) -> Vec<ScriptLine> {
+    // Edits are usually local, so only diff what lies between the common ends
+    let prefix = previous
+        .iter()
+        .zip(&lines)
+        .take_while(|(old, new)| old.same_content(new))
+        .count();
+    let suffix = previous[prefix..]
+        .iter()
+        .rev()
+        .zip(lines[prefix..].iter().rev())
+        .take_while(|(old, new)| old.same_content(new))
+        .count();
+    let old_middle = prefix..previous.len() - suffix;
+    let new_middle = prefix..lines.len() - suffix;
+
+    let mut pairs: Vec<(usize, usize)> = (0..prefix).map(|i| (i, i)).collect();
+    pairs.extend(
+        common_subsequence(&previous[old_middle.clone()], &lines[new_middle.clone()])
+            .into_iter()
+            .map(|(old, new)| (old_middle.start + old, new_middle.start + new)),
+    );
+    pairs.extend((0..suffix).map(|i| (old_middle.end + i, new_middle.end + i)));
+
+    for (old, new) in pairs {
+        let old = &previous[old];
+        let line = &mut lines[new];
+        line.id = old.id.clone();
+        line.status = old.status.clone();
+        line.output_path = old.output_path.clone();
+        line.takes = old.takes.clone();
+    }
+
+    lines
+}
+
+/// Index pairs of a longest common subsequence of equal-content lines.
+fn common_subsequence(old: &[ScriptLine], new: &[ScriptLine]) -> Vec<(usize, usize)> {
+    // lengths[i][j] is the LCS length of old[i..] and new[j..]
+    let mut lengths = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lengths[i][j] = if old[i].same_content(&new[j]) {
+                lengths[i + 1][j + 1] + 1
+            } else {
+                lengths[i + 1][j].max(lengths[i][j + 1])
+            };
+        }
+    }
+
+    let mut pairs = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() && j < new.len() {
+        if old[i].same_content(&new[j]) {
+            pairs.push((i, j));
+            i += 1;
+            j += 1;
+        } else if lengths[i + 1][j] >= lengths[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+    pairs
+}
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Severity {
+    /// The line was understood but may not do what the author meant.
+    Warning,
+    /// The line can't be synthesized; synthesis is refused until it is fixed.
+    Error,
+}
+
+/// A problem found while parsing a script, anchored to its source position.
+#[derive(Clone, PartialEq, Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    /// 1-based line number in the script text.
+    pub line: usize,
+    /// 0-based character columns within that line, end exclusive.
+    pub span: Range<usize>,
+    pub message: String,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_scenes() {
+        let lines = vec![
+            ScriptLine::new("Gandalf".to_string(), "Prologue.".to_string(), None),
+            ScriptLine::scene("Moria".to_string()),
+            ScriptLine::new("Gandalf".to_string(), "Fly!".to_string(), None),
+            ScriptLine::pause(1.0),
+            ScriptLine::scene("Lothlórien".to_string()),
+        ];
+
+        let scenes = group_scenes(&lines);
+        assert_eq!(
+            scenes,
+            vec![
+                SceneGroup { title: None, lines: 0..1 },
+                SceneGroup { title: Some("Moria".to_string()), lines: 1..4 },
+                SceneGroup { title: Some("Lothlórien".to_string()), lines: 4..5 },
+            ]
+        );
+        assert!(group_scenes(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_reconcile_keeps_unchanged_lines() {
+        let line = |text: &str| ScriptLine::new("Gandalf".to_string(), text.to_string(), None);
+        let mut previous = vec![line("One."), line("Two."), line("Three."), line("Four.")];
+        for (i, l) in previous.iter_mut().enumerate() {
+            l.status = SynthesisStatus::Done;
+            l.output_path = Some(format!("line_{}.wav", i));
+        }
+
+        // Edit the second line, insert one after the third, drop the last
+        let lines = reconcile_lines(&previous, vec![line("One."), line("Too."), line("Three."), line("New.")]);
+
+        assert_eq!(lines[0].id, previous[0].id);
+        assert_eq!(lines[0].status, SynthesisStatus::Done);
+        assert_eq!(lines[0].output_path.as_deref(), Some("line_0.wav"));
+        assert_ne!(lines[1].id, previous[1].id);
+        assert_eq!(lines[1].status, SynthesisStatus::Idle);
+        assert_eq!(lines[2].id, previous[2].id);
+        assert_eq!(lines[2].output_path.as_deref(), Some("line_2.wav"));
+        assert_eq!(lines[3].status, SynthesisStatus::Idle);
+        assert!(lines[3].output_path.is_none());
+
+        // Lines shifted by an insertion keep their identity
+        let shifted = reconcile_lines(&previous, vec![line("Zero."), line("One."), line("Two.")]);
+        assert_eq!(shifted[1].id, previous[0].id);
+        assert_eq!(shifted[2].id, previous[1].id);
+        assert_eq!(shifted[0].status, SynthesisStatus::Idle);
+    }
+
+    #[test]
+    fn test_take_selection() {
+        let params = SynthesisParams { cfg_weight: 0.5, exaggeration: 0.5, model: "turbo".to_string(), seed: Some(10) };
+        assert_eq!(params.for_take(2).seed, Some(12));
+        assert_eq!(SynthesisParams { seed: None, ..params.clone() }.for_take(2).seed, None);
+
+        let mut line = ScriptLine::new("Gandalf".to_string(), "Fly!".to_string(), None);
+        line.takes = (0..3)
+            .map(|i| Take { path: format!("take{}.wav", i), params: params.for_take(i) })
+            .collect();
+        assert_eq!(line.selected_take(), None);
+
+        line.select_take(2);
+        assert_eq!(line.selected_take(), Some(2));
+        assert_eq!(line.output_path.as_deref(), Some("take2.wav"));
+        assert_eq!(line.status, SynthesisStatus::Done);
+        line.select_take(5);
+        assert_eq!(line.selected_take(), Some(2));
+    }
 }
@@ -6,6 +6,41 @@ pub struct Settings {
     pub voice_files_directory: String,
     pub projects_directory: String,
     pub active_project_id: Option<String>,
+    /// Longest text sent to the model in one request; longer lines are split
+    /// at sentence and clause boundaries. 0 disables splitting.
+    #[serde(default = "default_max_chunk_chars")]
+    pub max_chunk_chars: usize,
+    /// Size limit of the synthesis cache in megabytes; least recently used clips
+    /// are evicted beyond it.
+    #[serde(default = "default_cache_limit_mb")]
+    pub cache_limit_mb: u64,
+    /// Lines synthesized at the same time. Raise it when the backend can serve
+    /// several requests in parallel.
+    #[serde(default = "default_max_concurrent_jobs")]
+    pub max_concurrent_jobs: usize,
+    /// Retries of a request that failed to connect or got a 5xx response.
+    #[serde(default = "default_max_retries")]
+    pub max_retries: u32,
+    /// Keep synthesizing past lines that still fail after retries, and report
+    /// them at the end instead of stopping at the first.
+    #[serde(default)]
+    pub continue_on_error: bool,
+}
+
+fn default_max_chunk_chars() -> usize {
+    300
+}
+
+fn default_cache_limit_mb() -> u64 {
+    1024
+}
+
+fn default_max_concurrent_jobs() -> usize {
+    1
+}
+
+fn default_max_retries() -> u32 {
+    2
 }
 
 impl Default for Settings {
@@ -15,6 +50,11 @@ impl Default for Settings {
             voice_files_directory: String::from("frontend/data/voices"),
             projects_directory: String::from("frontend/data/projects"),
             active_project_id: None,
+            max_chunk_chars: default_max_chunk_chars(),
+            cache_limit_mb: default_cache_limit_mb(),
+            max_concurrent_jobs: default_max_concurrent_jobs(),
+            max_retries: default_max_retries(),
+            continue_on_error: false,
         }
     }
 }
@@ -1,6 +1,7 @@
-use crate::models::project::Project;
+use crate::models::project::{Project, ProjectConfig};
 use crate::models::character::Character;
 use crate::models::settings::Settings;
+use crate::models::script::SynthesisParams;
 
 const API_BASE_URL: &str = "http://localhost:8000";
 
@@ -83,11 +84,16 @@ pub async fn update_settings(settings: Settings) -> Result<Settings, String> {
         .map_err(|e| format!("Failed to parse settings: {}", e))
 }
 
-/// Fetch characters for the current active project
-pub async fn fetch_characters() -> Result<Vec<Character>, String> {
+/// Query string selecting a project other than the active one; empty for `None`.
+pub fn project_query(project_id: Option<&str>) -> String {
+    project_id.map(|id| format!("?project_id={}", urlencoding::encode(id))).unwrap_or_default()
+}
+
+/// Fetch characters for the given project, or for the current active project
+pub async fn fetch_characters(project_id: Option<&str>) -> Result<Vec<Character>, String> {
     let client = reqwest::Client::new();
     let response = client
-        .get(format!("{}/characters", API_BASE_URL))
+        .get(format!("{}/characters{}", API_BASE_URL, project_query(project_id)))
         .send()
         .await
         .map_err(|e| format!("Request failed: {}", e))?;
@@ -118,12 +124,56 @@ pub async fn backend_save_characters(characters: Vec<Character>) -> Result<(), S
     
     Ok(())
 }
+/// Fetch the script/synthesis preferences of the given project, or of the active project
+pub async fn fetch_project_config(project_id: Option<&str>) -> Result<ProjectConfig, String> {
+    let client = reqwest::Client::new();
+    let response = client
+        .get(format!("{}/project-config{}", API_BASE_URL, project_query(project_id)))
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Server error: {}", response.status()));
+    }
+    
+    response
+        .json::<ProjectConfig>()
+        .await
+        .map_err(|e| format!("Failed to parse project config: {}", e))
+}
+
+/// Save the script/synthesis preferences of the active project
+pub async fn save_project_config(config: ProjectConfig) -> Result<(), String> {
+    let client = reqwest::Client::new();
+    let response = client
+        .post(format!("{}/project-config", API_BASE_URL))
+        .json(&config)
+        .send()
+        .await
+        .map_err(|e| format!("Request failed: {}", e))?;
+    
+    if !response.status().is_success() {
+        return Err(format!("Server error: {}", response.status()));
+    }
+    
+    Ok(())
+}
+
+/// Models the backend can synthesize with.
+pub const SYNTHESIS_MODELS: [&str; 2] = ["chatterbox-turbo", "chatterbox"];
+
+/// Model used when a character doesn't pick one.
+pub const SYNTHESIS_MODEL: &str = SYNTHESIS_MODELS[0];
+
 #[derive(serde::Serialize)]
 pub struct SynthesisRequest {
     pub text: String,
     pub reference_audio_path: String,
     pub cfg_weight: f32,
     pub exaggeration: f32,
+    pub model: String,
+    pub seed: Option<u64>,
 }
 
 /// Call the backend synthesis API and save the result to a file
@@ -131,15 +181,16 @@ pub async fn synthesize_audio(
     text: String,
     reference_audio_path: String,
     output_path: String,
-    cfg_weight: f32,
-    exaggeration: f32,
+    params: &SynthesisParams,
 ) -> Result<String, String> {
     let client = reqwest::Client::new();
     let payload = SynthesisRequest {
         text,
         reference_audio_path,
-        cfg_weight,
-        exaggeration,
+        cfg_weight: params.cfg_weight,
+        exaggeration: params.exaggeration,
+        model: params.model.clone(),
+        seed: params.seed,
     };
     
     let response = client
@@ -164,6 +215,12 @@ pub async fn synthesize_audio(
     Ok(output_path)
 }
 
+/// Whether a `synthesize_audio` error may succeed on retry: the backend was
+/// unreachable or failed with a 5xx status.
+pub fn is_transient_error(error: &str) -> bool {
+    error.starts_with("Request failed") || error.starts_with("Server error: 5")
+}
+
 /// Check if the backend is running and responsive
 pub async fn check_backend_health() -> bool {
     let client = reqwest::Client::builder()
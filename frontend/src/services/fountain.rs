@@ -0,0 +1,409 @@
+use crate::models::character::Character;
+use crate::models::script::{LineKind, ScriptLine, PARAGRAPH_SEPARATOR};
+use crate::services::script_parser::{find_characters, parse_pause_directive, DEFAULT_NARRATOR_NAME};
+use crate::services::tags::canonical_tag;
+use regex::Regex;
+
+/// Keys that may open a Fountain title page.
+const TITLE_PAGE_KEYS: [&str; 10] = [
+    "title", "credit", "author", "authors", "source", "draft date", "date", "contact", "copyright", "notes",
+];
+
+/// Reads a Fountain screenplay into script lines.
+///
+/// - Character cues (`GANDALF`, `GANDALF (V.O.)`, `@Gandalf`) become speakers, matched
+///   against character names and aliases like `[Name]:` cues are
+/// - Parentheticals such as `(sighs)` or `(clears throat)` become the matching
+///   paralinguistic tag in the dialogue text; other parentheticals are dropped
+/// - Scene headings (`INT. BAG END - NIGHT`, `.PROLOGUE`) become scene markers
+/// - Action paragraphs become narration spoken by `narrator_id`, or by
+///   `DEFAULT_NARRATOR_NAME` when no narrator is bound
+/// - Notes holding a pause (`[[pause 1.5s]]`, `[[beat]]`) become pauses
+/// - Title pages, sections, synopses, transitions, other notes and boneyard
+///   comments are skipped
+pub fn parse_fountain(text: &str, characters: &[Character], narrator_id: Option<&str>) -> Vec<ScriptLine> {
+    let text = strip_boneyard(&text.replace("\r\n", "\n"));
+    let source: Vec<&str> = text.lines().collect();
+
+    let narrator = narrator_id.and_then(|id| characters.iter().find(|c| c.id == id));
+    let (narrator_name, narrator_id) = match narrator {
+        Some(c) => (c.name.clone(), Some(c.id.clone())),
+        None => (DEFAULT_NARRATOR_NAME.to_string(), None),
+    };
+
+    let mut lines = Vec::new();
+    let mut action: Vec<String> = Vec::new();
+    let flush_action = |lines: &mut Vec<ScriptLine>, action: &mut Vec<String>| {
+        if !action.is_empty() {
+            lines.push(ScriptLine::narration(narrator_name.clone(), action.join(" "), narrator_id.clone()));
+            action.clear();
+        }
+    };
+
+    let mut i = skip_title_page(&source);
+    while i < source.len() {
+        let trimmed = source[i].trim();
+        let prev_blank = i == 0 || source[i - 1].trim().is_empty();
+        let next_blank = source.get(i + 1).is_none_or(|l| l.trim().is_empty());
+
+        if trimmed.is_empty() {
+            flush_action(&mut lines, &mut action);
+            i += 1;
+            continue;
+        }
+
+        // Notes on their own line: pauses survive, anything else is dropped
+        if let Some(note) = trimmed.strip_prefix("[[").and_then(|t| t.strip_suffix("]]")) {
+            flush_action(&mut lines, &mut action);
+            if let Some(seconds) = parse_pause_directive(&format!("{{{}}}", note.trim())) {
+                lines.push(ScriptLine::pause(seconds));
+            }
+            i += 1;
+            continue;
+        }
+
+        // Sections, synopses and page breaks carry no audio
+        if trimmed.starts_with('#') || trimmed.starts_with('=') {
+            flush_action(&mut lines, &mut action);
+            i += 1;
+            continue;
+        }
+
+        if let Some(title) = scene_heading(trimmed).filter(|_| prev_blank) {
+            flush_action(&mut lines, &mut action);
+            lines.push(ScriptLine::scene(title));
+            i += 1;
+            continue;
+        }
+
+        if is_transition(trimmed, prev_blank, next_blank) {
+            flush_action(&mut lines, &mut action);
+            i += 1;
+            continue;
+        }
+
+        // A character cue followed by its dialogue block
+        if let Some(cue) = character_cue(trimmed).filter(|_| prev_blank && !next_blank) {
+            flush_action(&mut lines, &mut action);
+            i += 1;
+
+            let mut paragraphs: Vec<String> = Vec::new();
+            let mut current: Vec<String> = Vec::new();
+            while let Some(raw) = source.get(i) {
+                let text = raw.trim();
+                if text.is_empty() {
+                    // A line of two spaces keeps the dialogue going as a new paragraph
+                    if raw.len() < 2 {
+                        break;
+                    }
+                    if !current.is_empty() {
+                        paragraphs.push(current.join(" "));
+                        current.clear();
+                    }
+                } else if text.starts_with('(') && text.ends_with(')') {
+                    if let Some(tag) = parenthetical_tag(text) {
+                        current.push(format!("[{}]", tag));
+                    }
+                } else {
+                    current.push(strip_emphasis(text.trim_start_matches('~').trim()));
+                }
+                i += 1;
+            }
+            if !current.is_empty() {
+                paragraphs.push(current.join(" "));
+            }
+
+            let matches = find_characters(&cue, characters);
+            let (name, id) = match matches.as_slice() {
+                [character] => (character.name.clone(), Some(character.id.clone())),
+                _ => (cue, None),
+            };
+            lines.push(ScriptLine::new(name, paragraphs.join(PARAGRAPH_SEPARATOR), id));
+            continue;
+        }
+
+        // Everything else is action, with `!` forcing and `>centered<` markers removed
+        let text = trimmed.strip_prefix('!').unwrap_or(trimmed);
+        let text = text
+            .strip_prefix('>')
+            .and_then(|t| t.strip_suffix('<'))
+            .unwrap_or(text)
+            .trim();
+        action.push(strip_emphasis(text));
+        i += 1;
+    }
+    flush_action(&mut lines, &mut action);
+
+    lines
+}
+
+/// Writes script lines as a Fountain screenplay.
+///
+/// Dialogue gets an uppercase character cue, with leading paralinguistic tags turned
+/// into parentheticals; narration becomes action, scenes become headings and pauses
+/// become `[[pause 1.5s]]` notes, which `parse_fountain` reads back. Per-line cfg and
+/// exaggeration overrides have no Fountain equivalent and are not written.
+pub fn write_fountain(lines: &[ScriptLine]) -> String {
+    let mut blocks: Vec<String> = Vec::new();
+
+    for line in lines {
+        match &line.kind {
+            LineKind::Scene { title } => blocks.push(format_scene_heading(title)),
+            LineKind::Pause { seconds } => blocks.push(format!("[[pause {}s]]", seconds)),
+            LineKind::Narration => {
+                for paragraph in line.paragraphs() {
+                    blocks.push(format_action(paragraph));
+                }
+            }
+            LineKind::Dialogue => blocks.push(format_dialogue(line)),
+        }
+    }
+
+    let mut out = blocks.join("\n\n");
+    out.push('\n');
+    out
+}
+
+/// Removes `/* ... */` boneyard sections, which may span lines.
+fn strip_boneyard(text: &str) -> String {
+    let pattern = Regex::new(r"(?s)/\*.*?\*/").unwrap();
+    pattern.replace_all(text, "").into_owned()
+}
+
+/// Returns the index of the first line after the title page, if the script has one.
+fn skip_title_page(source: &[&str]) -> usize {
+    let is_title_key = |line: &str| {
+        line.split_once(':')
+            .is_some_and(|(key, _)| TITLE_PAGE_KEYS.contains(&key.trim().to_lowercase().as_str()))
+    };
+
+    match source.first() {
+        Some(first) if is_title_key(first) => source
+            .iter()
+            .position(|line| line.trim().is_empty())
+            .unwrap_or(source.len()),
+        _ => 0,
+    }
+}
+
+/// Returns the title of a scene heading such as `INT. HOUSE - DAY` or forced `.PROLOGUE`.
+fn scene_heading(line: &str) -> Option<String> {
+    let heading = if let Some(forced) = line.strip_prefix('.') {
+        // `...` is an ellipsis in action, not a forced heading
+        if forced.starts_with('.') {
+            return None;
+        }
+        forced
+    } else {
+        let pattern = Regex::new(r"(?i)^(int|ext|est|int\.?/ext|i/e)[. ]").unwrap();
+        if !pattern.is_match(line) {
+            return None;
+        }
+        line
+    };
+
+    // Drop a trailing scene number like `#12A#`
+    let number = Regex::new(r"\s*#[^#]*#\s*$").unwrap();
+    let title = number.replace(heading, "").trim().to_string();
+    (!title.is_empty()).then_some(title)
+}
+
+/// Whether a line is a transition (`CUT TO:`, forced `> FADE OUT.`), which is skipped.
+fn is_transition(line: &str, prev_blank: bool, next_blank: bool) -> bool {
+    if line.starts_with('>') && !line.ends_with('<') {
+        return true;
+    }
+    prev_blank && next_blank && line.ends_with("TO:") && !line.chars().any(char::is_lowercase)
+}
+
+/// Returns the speaker of a character cue, without extensions like `(V.O.)` or the
+/// dual-dialogue caret. Cues are all uppercase unless forced with `@`.
+fn character_cue(line: &str) -> Option<String> {
+    let (cue, forced) = match line.strip_prefix('@') {
+        Some(rest) => (rest, true),
+        None => (line, false),
+    };
+
+    let cue = cue.trim_end().trim_end_matches('^').trim_end();
+    let name = match cue.find('(') {
+        Some(open) if cue.ends_with(')') => &cue[..open],
+        _ => cue,
+    }
+    .trim();
+
+    if name.is_empty() || !name.chars().any(char::is_alphabetic) {
+        return None;
+    }
+    if !forced && (name.chars().any(char::is_lowercase) || line.starts_with('!')) {
+        return None;
+    }
+    Some(name.to_string())
+}
+
+/// Maps a parenthetical like `(sighs)`, `(laughing)` or `(clears throat)` to a
+/// paralinguistic tag.
+fn parenthetical_tag(parenthetical: &str) -> Option<&'static str> {
+    canonical_tag(parenthetical.trim_start_matches('(').trim_end_matches(')'))
+}
+
+/// Removes `*italic*`, `**bold**` and `_underline_` markers, keeping escaped `\*` and `\_`.
+fn strip_emphasis(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\\' if matches!(chars.peek(), Some('*') | Some('_')) => out.push(chars.next().unwrap()),
+            '*' | '_' => {}
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+fn format_scene_heading(title: &str) -> String {
+    match scene_heading(title) {
+        Some(_) if !title.starts_with('.') => title.to_uppercase(),
+        _ => format!(".{}", title),
+    }
+}
+
+/// Writes an action paragraph, forcing it with `!` where it would read as another element.
+fn format_action(text: &str) -> String {
+    let ambiguous = scene_heading(text).is_some()
+        || character_cue(text).is_some()
+        || text.starts_with(['#', '=', '>', '@', '.', '~', '!'])
+        || text.starts_with("[[");
+    if ambiguous {
+        format!("!{}", text)
+    } else {
+        text.to_string()
+    }
+}
+
+fn format_dialogue(line: &ScriptLine) -> String {
+    let upper = line.character_name.to_uppercase();
+    let cue = if character_cue(&upper).as_deref() == Some(upper.as_str()) {
+        upper
+    } else {
+        format!("@{}", line.character_name)
+    };
+
+    let leading_tag = Regex::new(r"^\[([^\]]+)\]\s*").unwrap();
+    let mut out = vec![cue];
+    for (index, paragraph) in line.paragraphs().into_iter().enumerate() {
+        if index > 0 {
+            out.push("  ".to_string());
+        }
+        let mut text = paragraph;
+        while let Some(captures) = leading_tag.captures(text) {
+            out.push(format!("({})", &captures[1]));
+            text = &text[captures.get(0).unwrap().end()..];
+        }
+        if !text.is_empty() {
+            out.push(text.to_string());
+        }
+    }
+    out.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "Title: The Fellowship\nAuthor: J.R.R.\n\nINT. BAG END - NIGHT #1#\n\nA fire crackles. *Gandalf* waits.\nThe ring lies on the table.\n\nGANDALF (V.O.)\n(sighs)\nKeep it secret.\n(quietly)\nKeep it safe.\n\n[[pause 1.5s]]\n\n[[TODO: fix this line]]\n\nFRODO ^\nI will.\n  \nI promise.\n\nCUT TO:\n\n/* cut scene\nSAM\nHello.\n*/\n.PROLOGUE\n";
+
+    fn characters() -> Vec<Character> {
+        let mut narrator = Character::new("Storyteller".to_string());
+        narrator.id = "narrator".to_string();
+        vec![
+            Character::new("Gandalf".to_string()),
+            Character::new("Frodo".to_string()),
+            narrator,
+        ]
+    }
+
+    #[test]
+    fn test_parse_fountain_elements() {
+        let characters = characters();
+        let lines = parse_fountain(SAMPLE, &characters, Some("narrator"));
+
+        let kinds: Vec<&LineKind> = lines.iter().map(|l| &l.kind).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                &LineKind::Scene { title: "INT. BAG END - NIGHT".to_string() },
+                &LineKind::Narration,
+                &LineKind::Dialogue,
+                &LineKind::Pause { seconds: 1.5 },
+                &LineKind::Dialogue,
+                &LineKind::Scene { title: "PROLOGUE".to_string() },
+            ]
+        );
+
+        assert_eq!(lines[1].character_name, "Storyteller");
+        assert_eq!(lines[1].text, "A fire crackles. Gandalf waits. The ring lies on the table.");
+
+        assert_eq!(lines[2].character_name, "Gandalf");
+        assert!(lines[2].character_id.is_some());
+        assert_eq!(lines[2].text, "[sigh] Keep it secret. Keep it safe.");
+
+        assert_eq!(lines[4].character_name, "Frodo");
+        assert_eq!(lines[4].paragraphs(), vec!["I will.", "I promise."]);
+    }
+
+    #[test]
+    fn test_parse_fountain_unbound_narrator_and_unknown_speaker() {
+        let lines = parse_fountain("The door opens.\n\nSAM\nMr. Frodo!\n", &characters(), None);
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].character_name, DEFAULT_NARRATOR_NAME);
+        assert!(lines[0].character_id.is_none());
+        assert_eq!(lines[1].character_name, "SAM");
+        assert!(lines[1].character_id.is_none());
+    }
+
+    #[test]
+    fn test_parenthetical_tags() {
+        assert_eq!(parenthetical_tag("(sighs)"), Some("sigh"));
+        assert_eq!(parenthetical_tag("(Laughing)"), Some("laugh"));
+        assert_eq!(parenthetical_tag("(chuckling)"), Some("chuckle"));
+        assert_eq!(parenthetical_tag("(clears throat)"), Some("clear throat"));
+        assert_eq!(parenthetical_tag("(shushes)"), Some("shush"));
+        assert_eq!(parenthetical_tag("(to Frodo)"), None);
+    }
+
+    #[test]
+    fn test_write_fountain_round_trip() {
+        let characters = characters();
+        let lines = parse_fountain(SAMPLE, &characters, Some("narrator"));
+
+        let written = write_fountain(&lines);
+        assert!(written.starts_with("INT. BAG END - NIGHT\n\n"));
+        assert!(written.contains("GANDALF\n(sigh)\nKeep it secret."));
+        assert!(written.contains("[[pause 1.5s]]"));
+        assert!(written.contains(".PROLOGUE"));
+
+        let reread = parse_fountain(&written, &characters, Some("narrator"));
+        assert_eq!(summarize(&reread), summarize(&lines));
+    }
+
+    /// Line IDs are generated per parse, so compare everything else.
+    fn summarize(lines: &[ScriptLine]) -> Vec<(LineKind, String, Option<String>, String)> {
+        lines
+            .iter()
+            .map(|l| (l.kind.clone(), l.character_name.clone(), l.character_id.clone(), l.text.clone()))
+            .collect()
+    }
+
+    #[test]
+    fn test_write_fountain_forces_ambiguous_action() {
+        let lines = vec![
+            ScriptLine::narration("Narrator".to_string(), "EXT. SHIRE".to_string(), None),
+            ScriptLine::narration("Narrator".to_string(), "BOOM".to_string(), None),
+            ScriptLine::new("Gandalf the Grey".to_string(), "Hm.".to_string(), None),
+        ];
+
+        let written = write_fountain(&lines);
+        assert_eq!(written, "!EXT. SHIRE\n\n!BOOM\n\nGANDALF THE GREY\nHm.\n");
+    }
+}
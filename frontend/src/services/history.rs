@@ -1,9 +1,11 @@
 use crate::models::history::ScriptHistory;
-use std::collections::HashMap;
+use crate::services::api::project_query;
+use std::collections::{BTreeMap, HashMap};
 
-pub async fn fetch_history() -> Result<Vec<ScriptHistory>, String> {
+/// Fetch the saved scripts of the given project, or of the active project
+pub async fn fetch_history(project_id: Option<&str>) -> Result<Vec<ScriptHistory>, String> {
     match reqwest::Client::new()
-        .get("http://localhost:8000/history")
+        .get(format!("http://localhost:8000/history{}", project_query(project_id)))
         .send()
         .await
     {
@@ -26,6 +28,7 @@ pub async fn save_to_history(
     script_text: String,
     audio_path: String,
     character_mappings: HashMap<String, String>,
+    variables: BTreeMap<String, String>,
 ) -> Result<ScriptHistory, String> {
     #[derive(serde::Serialize)]
     struct CreateHistoryRequest {
@@ -33,6 +36,7 @@ pub async fn save_to_history(
         script_text: String,
         audio_path: String,
         character_mappings: HashMap<String, String>,
+        variables: BTreeMap<String, String>,
     }
 
     let payload = CreateHistoryRequest {
@@ -40,6 +44,7 @@ pub async fn save_to_history(
         script_text,
         audio_path,
         character_mappings,
+        variables,
     };
 
     match reqwest::Client::new()
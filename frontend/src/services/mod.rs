@@ -3,3 +3,12 @@ pub mod script_parser;
 pub mod api;
 pub mod history;
 pub mod audio_api;
+pub mod fountain;
+pub mod subtitles;
+pub mod text_chunker;
+pub mod pronunciation;
+pub mod text_normalizer;
+pub mod tags;
+pub mod synthesis_cache;
+pub mod run_control;
+pub mod synthesis_pipeline;
@@ -0,0 +1,222 @@
+use crate::models::project::PronunciationRule;
+use regex::{Captures, Regex};
+use std::path::Path;
+
+/// Column order of the CSV format read and written by this module.
+const CSV_HEADER: &str = "word,respelling,whole_word,case_sensitive";
+
+/// Rewrites `text` with the project's pronunciation rules.
+///
+/// All rules are applied in a single pass, so a respelling is never rewritten by
+/// another rule. Where rules overlap, the longest word wins (`Baldur's Gate` before
+/// `Baldur`). Paralinguistic tags like `[sigh]` are left untouched.
+pub fn apply_pronunciations(text: &str, rules: &[PronunciationRule]) -> String {
+    let mut rules: Vec<&PronunciationRule> = rules.iter().filter(|r| !r.word.trim().is_empty()).collect();
+    if rules.is_empty() {
+        return text.to_string();
+    }
+    rules.sort_by_key(|r| std::cmp::Reverse(r.word.chars().count()));
+
+    // Group 1 matches tags so they are copied through; group i + 2 is rule i
+    let mut alternatives = vec![r"(\[[^\]]*\])".to_string()];
+    alternatives.extend(rules.iter().map(|rule| format!("({})", rule_pattern(rule))));
+    let pattern = Regex::new(&alternatives.join("|")).unwrap();
+
+    pattern
+        .replace_all(text, |captures: &Captures| {
+            match (2..captures.len()).find(|&i| captures.get(i).is_some()) {
+                Some(i) => rules[i - 2].respelling.clone(),
+                None => captures[0].to_string(),
+            }
+        })
+        .into_owned()
+}
+
+/// Regex for one rule's word, with `\b` anchors on the ends that are word characters.
+fn rule_pattern(rule: &PronunciationRule) -> String {
+    let word = rule.word.trim();
+    let mut pattern = regex::escape(word);
+    if rule.whole_word {
+        if word.starts_with(|c: char| c.is_alphanumeric() || c == '_') {
+            pattern = format!(r"\b{}", pattern);
+        }
+        if word.ends_with(|c: char| c.is_alphanumeric() || c == '_') {
+            pattern = format!(r"{}\b", pattern);
+        }
+    }
+    if rule.case_sensitive {
+        pattern
+    } else {
+        format!("(?i:{})", pattern)
+    }
+}
+
+/// Reads rules from a `.json` or `.csv` file, chosen by extension.
+pub fn import_rules(path: &Path) -> Result<Vec<PronunciationRule>, String> {
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    if is_csv(path) {
+        rules_from_csv(&contents)
+    } else {
+        serde_json::from_str(&contents).map_err(|e| format!("Invalid pronunciation JSON: {}", e))
+    }
+}
+
+/// Writes rules to a `.json` or `.csv` file, chosen by extension.
+pub fn export_rules(path: &Path, rules: &[PronunciationRule]) -> Result<(), String> {
+    let contents = if is_csv(path) {
+        rules_to_csv(rules)
+    } else {
+        serde_json::to_string_pretty(rules).map_err(|e| format!("Failed to serialize rules: {}", e))?
+    };
+    std::fs::write(path, contents).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+}
+
+fn is_csv(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("csv"))
+}
+
+/// Formats rules as CSV with a header row, quoting fields where needed.
+pub fn rules_to_csv(rules: &[PronunciationRule]) -> String {
+    let mut out = format!("{}\n", CSV_HEADER);
+    for rule in rules {
+        out.push_str(&format!(
+            "{},{},{},{}\n",
+            csv_field(&rule.word),
+            csv_field(&rule.respelling),
+            rule.whole_word,
+            rule.case_sensitive
+        ));
+    }
+    out
+}
+
+/// Parses CSV rules. The header row is optional; the flag columns may be omitted
+/// and default to whole-word, case-insensitive matching.
+pub fn rules_from_csv(contents: &str) -> Result<Vec<PronunciationRule>, String> {
+    let mut rules = Vec::new();
+
+    for (index, line) in contents.lines().enumerate() {
+        if line.trim().is_empty() || (index == 0 && line.trim().eq_ignore_ascii_case(CSV_HEADER)) {
+            continue;
+        }
+        let fields = split_csv_line(line).map_err(|e| format!("Line {}: {}", index + 1, e))?;
+        let [word, respelling, flags @ ..] = fields.as_slice() else {
+            return Err(format!("Line {}: expected at least word and respelling", index + 1));
+        };
+
+        let mut rule = PronunciationRule::new(word.clone(), respelling.clone());
+        if let Some(value) = flags.first() {
+            rule.whole_word = parse_flag(value).map_err(|e| format!("Line {}: {}", index + 1, e))?;
+        }
+        if let Some(value) = flags.get(1) {
+            rule.case_sensitive = parse_flag(value).map_err(|e| format!("Line {}: {}", index + 1, e))?;
+        }
+        rules.push(rule);
+    }
+
+    Ok(rules)
+}
+
+fn parse_flag(value: &str) -> Result<bool, String> {
+    match value.trim().to_ascii_lowercase().as_str() {
+        "" | "true" | "yes" | "1" => Ok(true),
+        "false" | "no" | "0" => Ok(false),
+        other => Err(format!("'{}' is not true or false", other)),
+    }
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+/// Splits one CSV row, handling quoted fields with doubled quotes.
+fn split_csv_line(line: &str) -> Result<Vec<String>, String> {
+    let mut fields = Vec::new();
+    let mut field = String::new();
+    let mut chars = line.chars().peekable();
+    let mut quoted = false;
+
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            '"' if quoted => quoted = false,
+            '"' if field.trim().is_empty() => {
+                field.clear();
+                quoted = true;
+            }
+            ',' if !quoted => fields.push(std::mem::take(&mut field).trim().to_string()),
+            _ => field.push(c),
+        }
+    }
+    if quoted {
+        return Err("unterminated quoted field".to_string());
+    }
+    fields.push(field.trim().to_string());
+
+    Ok(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rule(word: &str, respelling: &str) -> PronunciationRule {
+        PronunciationRule::new(word.to_string(), respelling.to_string())
+    }
+
+    /// The rules without their IDs, which CSV doesn't carry.
+    fn fields(rules: Vec<PronunciationRule>) -> Vec<(String, String, bool, bool)> {
+        rules.into_iter().map(|r| (r.word, r.respelling, r.whole_word, r.case_sensitive)).collect()
+    }
+
+    #[test]
+    fn test_apply_whole_word_case_insensitive() {
+        let rules = vec![rule("Drizzt", "Drizzit"), rule("Tiamat", "Tee-ah-maht")];
+
+        assert_eq!(
+            apply_pronunciations("DRIZZT bows to tiamat, not Drizztian.", &rules),
+            "Drizzit bows to Tee-ah-maht, not Drizztian."
+        );
+    }
+
+    #[test]
+    fn test_apply_options_and_priority() {
+        let mut partial = rule("Gate", "Gayt");
+        partial.whole_word = false;
+        let mut exact = rule("Tiamat", "Tee-ah-maht");
+        exact.case_sensitive = true;
+        let rules = vec![partial, rule("Baldur's Gate", "Bal-dur's Gayt"), exact, rule("sigh", "sai")];
+
+        assert_eq!(
+            apply_pronunciations("Baldur's Gate, Gateway, tiamat, Tiamat [sigh]", &rules),
+            "Bal-dur's Gayt, Gaytway, tiamat, Tee-ah-maht [sigh]"
+        );
+        // Respellings aren't rewritten by other rules
+        let rules = vec![rule("a", "b"), rule("b", "c")];
+        assert_eq!(apply_pronunciations("a b", &rules), "b c");
+    }
+
+    #[test]
+    fn test_csv_round_trip() {
+        let mut quoted = rule("Baldur's Gate", "Bal-dur, \"the\" Gate");
+        quoted.case_sensitive = true;
+        let rules = vec![rule("Drizzt", "Drizzit"), quoted];
+
+        let csv = rules_to_csv(&rules);
+        assert!(csv.starts_with(CSV_HEADER));
+        assert_eq!(fields(rules_from_csv(&csv).unwrap()), fields(rules));
+
+        // Header and flag columns are optional
+        assert_eq!(fields(rules_from_csv("Drizzt, Drizzit\n").unwrap()), fields(vec![rule("Drizzt", "Drizzit")]));
+        assert!(rules_from_csv("Drizzt").is_err());
+        assert!(rules_from_csv("Drizzt,Drizzit,maybe").is_err());
+    }
+}
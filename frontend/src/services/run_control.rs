@@ -0,0 +1,106 @@
+use std::sync::Arc;
+use tokio::sync::watch;
+
+/// Where a synthesis run stands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunState {
+    Running,
+    /// No new lines are started; requests already in flight finish.
+    Paused,
+    /// The run stops and in-flight requests are aborted. Final.
+    Cancelled,
+}
+
+/// Pause and cancellation token for one synthesis run. Clones share the same
+/// state, so the UI keeps one clone and the synthesis task checks another.
+#[derive(Debug, Clone)]
+pub struct RunControl {
+    state: Arc<watch::Sender<RunState>>,
+}
+
+impl Default for RunControl {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RunControl {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(watch::Sender::new(RunState::Running)),
+        }
+    }
+
+    pub fn state(&self) -> RunState {
+        *self.state.borrow()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.state() == RunState::Cancelled
+    }
+
+    /// Stops starting new lines. Has no effect on a cancelled run.
+    pub fn pause(&self) {
+        self.transition(RunState::Paused);
+    }
+
+    /// Starts new lines again. Has no effect on a cancelled run.
+    pub fn resume(&self) {
+        self.transition(RunState::Running);
+    }
+
+    pub fn cancel(&self) {
+        self.transition(RunState::Cancelled);
+    }
+
+    /// Waits until the run is cancelled.
+    pub async fn cancelled(&self) {
+        let mut receiver = self.state.subscribe();
+        // Only fails when the sender is gone, which `self` prevents
+        let _ = receiver.wait_for(|state| *state == RunState::Cancelled).await;
+    }
+
+    /// Waits out a pause. Returns `Running` once resumed, or `Cancelled`.
+    pub async fn wait_while_paused(&self) -> RunState {
+        let mut receiver = self.state.subscribe();
+        match receiver.wait_for(|state| *state != RunState::Paused).await {
+            Ok(state) => *state,
+            Err(_) => RunState::Cancelled,
+        }
+    }
+
+    fn transition(&self, next: RunState) {
+        self.state.send_if_modified(|state| {
+            let changed = *state != RunState::Cancelled && *state != next;
+            if changed {
+                *state = next;
+            }
+            changed
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_pause_resume_and_cancel() {
+        let control = RunControl::new();
+        let task_side = control.clone();
+
+        control.pause();
+        assert_eq!(task_side.state(), RunState::Paused);
+
+        let waiter = tokio::spawn(async move { task_side.wait_while_paused().await });
+        control.resume();
+        assert_eq!(waiter.await.unwrap(), RunState::Running);
+
+        control.cancel();
+        control.resume();
+        assert!(control.is_cancelled());
+        // Resolves at once for a cancelled run
+        control.cancelled().await;
+        assert_eq!(control.wait_while_paused().await, RunState::Cancelled);
+    }
+}
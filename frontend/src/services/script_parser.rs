@@ -1,55 +1,635 @@
 use crate::models::character::Character;
-use crate::models::script::ScriptLine;
+use crate::models::project::ProjectConfig;
+use crate::models::script::{Diagnostic, LineKind, LineOverrides, ScriptLine, Severity, PARAGRAPH_SEPARATOR, PARAMETER_RANGE};
+use crate::services::tags::{is_known_tag, suggest_tag, PARALINGUISTIC_TAGS};
 use regex::Regex;
+use std::collections::BTreeMap;
+use std::ops::Range;
+use std::sync::LazyLock;
+
+/// Speaker name shown for narration when no narrator character is bound.
+pub const DEFAULT_NARRATOR_NAME: &str = "Narrator";
+
+/// Length of a `{beat}` directive, in seconds.
+pub const BEAT_SECONDS: f32 = 1.0;
+
+/// A dialogue line, `[Name]: text`. Captures: 1 = cue, 2 = dialogue text.
+static CUE_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"^\s*\[([^\]]+)\]\s*:\s*(.*)\s*$").unwrap());
+
+/// What to do with lines that don't match the `[CharacterName]: text` pattern.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum UnmarkedLines {
+    /// Drop them, as stage directions and notes.
+    #[default]
+    Ignore,
+    /// Voice them with the given narrator character.
+    Narrate { narrator_id: Option<String> },
+}
+
+/// Options controlling how `parse_script_with_options` interprets a script.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParseOptions {
+    pub unmarked_lines: UnmarkedLines,
+    /// Values for `{{name}}` placeholders, substituted before each line is parsed.
+    pub variables: BTreeMap<String, String>,
+}
+
+impl ParseOptions {
+    /// Builds parse options from the active project's configuration.
+    pub fn from_config(config: &ProjectConfig) -> Self {
+        let unmarked_lines = if config.narrate_unmarked_lines {
+            UnmarkedLines::Narrate {
+                narrator_id: config.narrator_character_id.clone(),
+            }
+        } else {
+            UnmarkedLines::Ignore
+        };
+        Self {
+            unmarked_lines,
+            variables: config.variables.clone(),
+        }
+    }
+}
 
 /// Parses a script string into a vector of ScriptLine items.
 /// 
 /// The parser looks for lines matching the pattern: `[CharacterName]: dialogue text`
-/// - Lines that don't match this pattern are ignored
-/// - Empty lines are ignored
-/// - Character names are matched case-insensitively against the provided character list
+/// - The cue may carry per-line parameters: `[CharacterName|exag=0.8,cfg=0.3]: text`
+/// - A line holding only `{pause 1.5s}`, `{pause 500ms}`, `{silence 2s}` or `{beat}` becomes a pause
+/// - A line starting with `## ` becomes a scene marker titled by the rest of the line
+/// - `{{name}}` placeholders are replaced with the project's variable values first
+/// - Indented lines continue the previous line; a blank line before them starts a new paragraph
+/// - A bare `[CharacterName]:` takes every following line until the next cue or directive
+/// - A line opening with a known tag like `[sigh]` continues the previous line
+/// - Other lines are ignored as stage directions and notes; use `parse_script_with_options`
+///   to narrate them instead
+/// - Empty lines separate paragraphs and are otherwise ignored
+/// - Character names are matched case-insensitively (Unicode case folding) against the
+///   names and aliases of the provided character list
 /// 
 /// # Arguments
 /// * `text` - The raw script text to parse
 /// * `characters` - A slice of available characters to validate against
 /// 
 /// # Returns
-/// A vector of ScriptLine items, one for each valid dialogue line found
+/// A vector of ScriptLine items in script order: dialogue lines, pauses and scene markers
 pub fn parse_script(text: &str, characters: &[Character]) -> Vec<ScriptLine> {
-    // Regex pattern: [Name]: Text
-    // Captures: 1 = character name, 2 = dialogue text
-    let line_pattern = Regex::new(r"^\s*\[([^\]]+)\]\s*:\s*(.+)\s*$").unwrap();
+    parse_script_with_options(text, characters, &ParseOptions::default()).lines
+}
+
+/// The outcome of parsing a script: the lines to synthesize plus any problems found.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ParseResult {
+    pub lines: Vec<ScriptLine>,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+impl ParseResult {
+    /// Whether any diagnostic is an error, which blocks synthesis.
+    pub fn has_errors(&self) -> bool {
+        self.diagnostics.iter().any(|d| d.severity == Severity::Error)
+    }
+    
+    fn report(&mut self, severity: Severity, line: usize, span: Range<usize>, message: String) {
+        self.diagnostics.push(Diagnostic { severity, line, span, message });
+    }
+}
+
+/// Parses a script like `parse_script`, but lets the caller decide what happens
+/// to lines outside the `[CharacterName]: text` format, and reports problems.
+///
+/// With `UnmarkedLines::Narrate`, each unmarked line becomes a narration
+/// `ScriptLine` spoken by the narrator. If the narrator is unbound or no longer
+/// exists, the line keeps `DEFAULT_NARRATOR_NAME` and no character ID.
+///
+/// Malformed cues (`[Gandalf] no colon`, `[]: text`), unknown characters, undefined
+/// `{{variables}}`, a tag used as a speaker (`[sigh]: text`) and an unbound narrator are
+/// reported as errors; unknown directives, unknown cue parameters, unknown or misspelled
+/// tags (with a suggestion where one is close) and empty dialogue as warnings. Each diagnostic carries the
+/// 1-based source line and the character columns of the offending text.
+pub fn parse_script_with_options(
+    text: &str,
+    characters: &[Character],
+    options: &ParseOptions,
+) -> ParseResult {
+    let narrator = match &options.unmarked_lines {
+        UnmarkedLines::Ignore => None,
+        UnmarkedLines::Narrate { narrator_id } => Some(
+            narrator_id
+                .as_ref()
+                .and_then(|id| characters.iter().find(|c| &c.id == id)),
+        ),
+    };
     
-    let mut script_lines = Vec::new();
+    let mut result = ParseResult::default();
+    // The last pushed line, while following lines may still continue it
+    let mut open_block: Option<OpenBlock> = None;
+    // Per source line, where substituted values sit, to report spans in source columns
+    let mut column_maps: Vec<ColumnMap> = Vec::new();
     
-    for line in text.lines() {
-        // Skip empty lines
+    for (index, source_line) in text.lines().enumerate() {
+        let line_number = index + 1;
+        
+        // Substitute variables; unknown placeholders stay in the text
+        let (resolved, column_map, undefined) = resolve_line(source_line, &options.variables);
+        column_maps.push(column_map);
+        for (span, name) in undefined {
+            result.report(
+                Severity::Error,
+                line_number,
+                span,
+                format!("Undefined variable '{{{{{}}}}}'", name),
+            );
+        }
+        let line = resolved.as_str();
+        
+        // Blank lines end a paragraph but not the block
         let trimmed = line.trim();
         if trimmed.is_empty() {
+            if let Some(block) = open_block.as_mut() {
+                block.paragraph_break = true;
+            }
+            continue;
+        }
+        
+        // Byte offset of the trimmed content, so spans point into the original line
+        let indent = line.len() - line.trim_start().len();
+        let whole_line = char_span(line, indent, indent + trimmed.len());
+        let is_directive = trimmed.starts_with('{') && trimmed.ends_with('}');
+        let is_scene = trimmed.starts_with("##");
+        let is_cue = CUE_PATTERN.is_match(trimmed);
+        // `[sigh] ...` is a tag, not a cue missing its colon
+        let leads_with_tag = !is_cue
+            && TAG_PATTERN
+                .captures(trimmed)
+                .is_some_and(|c| c.get(0).unwrap().start() == 0 && is_known_tag(&c[1]));
+        
+        // Continuation lines attach to the previous speaker
+        if let Some(block) = open_block.as_mut() {
+            // A bracket that doesn't open a known tag is a broken cue, not speech
+            let cue_like = trimmed.starts_with('[') && !leads_with_tag;
+            let continues = !is_directive
+                && !is_scene
+                && !is_cue
+                && !cue_like
+                && (block.explicit || leads_with_tag || indent > 0);
+            if continues {
+                check_tags(&mut result, line, line_number, indent);
+                let script_line = result.lines.last_mut().unwrap();
+                if script_line.text.is_empty() {
+                    script_line.text = trimmed.to_string();
+                } else {
+                    let separator = if block.paragraph_break { PARAGRAPH_SEPARATOR } else { " " };
+                    script_line.text.push_str(separator);
+                    script_line.text.push_str(trimmed);
+                }
+                block.paragraph_break = false;
+                continue;
+            }
+        }
+        close_block(&mut result, open_block.take());
+        
+        // Directives are whole lines wrapped in braces
+        if is_directive {
+            match parse_pause_directive(trimmed) {
+                Some(seconds) => result.lines.push(ScriptLine::pause(seconds)),
+                None => result.report(
+                    Severity::Warning,
+                    line_number,
+                    whole_line,
+                    format!("Unknown directive {}; expected {{pause 1.5s}} or {{beat}}", trimmed),
+                ),
+            }
+            continue;
+        }
+        
+        // Scene headings: `## Title`
+        if is_scene {
+            let title = trimmed.trim_start_matches('#').trim();
+            if title.is_empty() {
+                result.report(Severity::Warning, line_number, whole_line, "Scene heading has no title".to_string());
+            } else {
+                result.lines.push(ScriptLine::scene(title.to_string()));
+            }
             continue;
         }
         
         // Try to match the dialogue pattern
-        if let Some(captures) = line_pattern.captures(trimmed) {
-            let character_name = captures.get(1).unwrap().as_str().trim().to_string();
-            let dialogue_text = captures.get(2).unwrap().as_str().trim().to_string();
+        if let Some(captures) = CUE_PATTERN.captures(trimmed) {
+            let cue = captures.get(1).unwrap();
+            // Include the surrounding brackets in the span
+            let cue_span = char_span(line, indent + cue.start() - 1, indent + cue.end() + 1);
+            let (character_name, overrides, problems) = split_cue(cue.as_str());
+            let dialogue = captures.get(2).unwrap();
+            let dialogue_text = dialogue.as_str().trim().to_string();
+            
+            for problem in problems {
+                result.report(Severity::Warning, line_number, cue_span.clone(), problem);
+            }
+            check_tags(&mut result, line, line_number, indent + dialogue.start());
+            
+            if character_name.is_empty() {
+                result.report(Severity::Error, line_number, cue_span, "Speaker name is empty".to_string());
+                continue;
+            }
             
-            // Find matching character (case-insensitive)
-            let character_id = characters
-                .iter()
-                .find(|c| c.name.eq_ignore_ascii_case(&character_name))
-                .map(|c| c.id.clone());
+            // Find matching character by name or alias (Unicode case-insensitive)
+            let matches = find_characters(&character_name, characters);
+            let character_id = match matches.as_slice() {
+                [character] => Some(character.id.clone()),
+                [] if is_known_tag(&character_name) => {
+                    result.report(
+                        Severity::Error,
+                        line_number,
+                        cue_span.clone(),
+                        format!(
+                            "[{}] is a tag, not a speaker; put it after the cue, e.g. [Name]: [{}] ...",
+                            character_name, character_name
+                        ),
+                    );
+                    None
+                }
+                [] => {
+                    result.report(
+                        Severity::Error,
+                        line_number,
+                        cue_span.clone(),
+                        format!("Unknown character '{}'", character_name),
+                    );
+                    None
+                }
+                several => {
+                    let names: Vec<&str> = several.iter().map(|c| c.name.as_str()).collect();
+                    result.report(
+                        Severity::Error,
+                        line_number,
+                        cue_span.clone(),
+                        format!("'{}' matches several characters: {}", character_name, names.join(", ")),
+                    );
+                    None
+                }
+            };
             
-            script_lines.push(ScriptLine::new(
+            let explicit = dialogue_text.is_empty();
+            let mut script_line = ScriptLine::new(
                 character_name,
                 dialogue_text,
                 character_id,
+            );
+            script_line.overrides = overrides;
+            result.lines.push(script_line);
+            
+            // A bare `[Name]:` opens a block that takes every following line
+            open_block = Some(OpenBlock {
+                explicit,
+                paragraph_break: false,
+                cue_line: line_number,
+                cue_span,
+            });
+        } else if trimmed.starts_with('[') && !leads_with_tag {
+            let (span, message) = diagnose_malformed_cue(line, indent);
+            result.report(Severity::Error, line_number, span, message);
+        } else if let Some(narrator) = narrator {
+            check_tags(&mut result, line, line_number, indent);
+            let (name, id) = match narrator {
+                Some(c) => (c.name.clone(), Some(c.id.clone())),
+                None => {
+                    result.report(
+                        Severity::Error,
+                        line_number,
+                        whole_line.clone(),
+                        "No narrator character is bound for unmarked lines".to_string(),
+                    );
+                    (DEFAULT_NARRATOR_NAME.to_string(), None)
+                }
+            };
+            result.lines.push(ScriptLine::narration(name, trimmed.to_string(), id));
+            open_block = Some(OpenBlock {
+                explicit: false,
+                paragraph_break: false,
+                cue_line: line_number,
+                cue_span: whole_line,
+            });
+        } else if leads_with_tag {
+            result.report(
+                Severity::Warning,
+                line_number,
+                whole_line,
+                "Tag is outside any dialogue and will be ignored".to_string(),
+            );
+        }
+        // Otherwise non-matching lines are silently ignored (as per spec)
+    }
+    close_block(&mut result, open_block);
+    
+    // Spans were measured on the resolved lines
+    for diagnostic in result.diagnostics.iter_mut() {
+        if let Some(map) = column_maps.get(diagnostic.line - 1) {
+            diagnostic.span = map.source_span(&diagnostic.span);
+        }
+    }
+    
+    result
+}
+
+/// A bracketed tag within dialogue text, like `[sigh]`.
+static TAG_PATTERN: LazyLock<Regex> = LazyLock::new(|| Regex::new(r"\[([^\[\]]*)\]").unwrap());
+
+/// Warns about bracketed tags from byte `start` of `line` on that aren't in the
+/// tag registry, suggesting the closest known tag.
+fn check_tags(result: &mut ParseResult, line: &str, line_number: usize, start: usize) {
+    for captures in TAG_PATTERN.captures_iter(&line[start..]) {
+        let name = captures[1].trim();
+        if name.is_empty() || is_known_tag(name) {
+            continue;
+        }
+        let tag = captures.get(0).unwrap();
+        let message = match suggest_tag(name) {
+            Some(known) => format!("Unknown tag [{}]; did you mean [{}]?", name, known),
+            None => format!("Unknown tag [{}]; known tags are [{}]", name, PARALINGUISTIC_TAGS.join("], [")),
+        };
+        result.report(
+            Severity::Warning,
+            line_number,
+            char_span(line, start + tag.start(), start + tag.end()),
+            message,
+        );
+    }
+}
+
+/// A `{{name}}` placeholder; names are letters, digits, `_` and `-`.
+static VARIABLE_PATTERN: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"\{\{\s*([A-Za-z_][\w-]*)\s*\}\}").unwrap());
+
+/// Substituted placeholders in one line, as (resolved columns, source columns).
+#[derive(Default)]
+struct ColumnMap(Vec<(Range<usize>, Range<usize>)>);
+
+impl ColumnMap {
+    /// Maps a span in the resolved line back to source columns. A span touching a
+    /// substituted value is widened to cover its whole placeholder.
+    fn source_span(&self, span: &Range<usize>) -> Range<usize> {
+        self.source_column(span.start, false)..self.source_column(span.end, true)
+    }
+    
+    fn source_column(&self, column: usize, is_end: bool) -> usize {
+        let mut shift = 0isize;
+        for (resolved, source) in &self.0 {
+            let before = if is_end { column <= resolved.start } else { column < resolved.start };
+            if before {
+                break;
+            }
+            let inside = if is_end { column <= resolved.end } else { column < resolved.end };
+            if inside {
+                return if is_end { source.end } else { source.start };
+            }
+            shift = source.end as isize - resolved.end as isize;
+        }
+        column.saturating_add_signed(shift)
+    }
+}
+
+/// Replaces the defined `{{name}}` placeholders in one line. Returns the resolved
+/// line, where the substitutions landed, and the resolved-column spans and names of
+/// undefined placeholders, which are left as written.
+fn resolve_line(
+    line: &str,
+    variables: &BTreeMap<String, String>,
+) -> (String, ColumnMap, Vec<(Range<usize>, String)>) {
+    if !line.contains("{{") {
+        return (line.to_string(), ColumnMap::default(), Vec::new());
+    }
+    
+    let mut resolved = String::with_capacity(line.len());
+    let mut map = ColumnMap::default();
+    let mut undefined = Vec::new();
+    let mut last = 0;
+    
+    for captures in VARIABLE_PATTERN.captures_iter(line) {
+        let placeholder = captures.get(0).unwrap();
+        let name = &captures[1];
+        resolved.push_str(&line[last..placeholder.start()]);
+        last = placeholder.end();
+        
+        let start = resolved.chars().count();
+        match variables.get(name) {
+            Some(value) => {
+                let source_start = line[..placeholder.start()].chars().count();
+                let source = source_start..source_start + placeholder.as_str().chars().count();
+                map.0.push((start..start + value.chars().count(), source));
+                resolved.push_str(value);
+            }
+            None => {
+                undefined.push((start..start + placeholder.as_str().chars().count(), name.to_string()));
+                resolved.push_str(placeholder.as_str());
+            }
+        }
+    }
+    resolved.push_str(&line[last..]);
+    
+    (resolved, map, undefined)
+}
+
+/// The defined variables a script uses, with their current values.
+pub fn referenced_variables(text: &str, variables: &BTreeMap<String, String>) -> BTreeMap<String, String> {
+    VARIABLE_PATTERN
+        .captures_iter(text)
+        .filter_map(|captures| {
+            let name = &captures[1];
+            variables.get(name).map(|value| (name.to_string(), value.clone()))
+        })
+        .collect()
+}
+
+/// The most recent line while following lines may still be appended to it.
+struct OpenBlock {
+    /// Opened by a bare `[Name]:` cue, so unindented lines continue it too.
+    explicit: bool,
+    /// A blank line was seen since the last appended text.
+    paragraph_break: bool,
+    cue_line: usize,
+    cue_span: Range<usize>,
+}
+
+/// Drops a block that ended without any text, warning about its cue.
+fn close_block(result: &mut ParseResult, block: Option<OpenBlock>) {
+    let Some(block) = block else {
+        return;
+    };
+    if result.lines.last().is_some_and(|line| line.text.is_empty()) {
+        let line = result.lines.pop().unwrap();
+        result.report(
+            Severity::Warning,
+            block.cue_line,
+            block.cue_span,
+            format!("[{}] has no dialogue text", line.character_name),
+        );
+    }
+}
+
+/// Folds a name for case-insensitive comparison: Unicode lowercase with the common
+/// full-folding cases (`ß` → `ss`, final sigma) and runs of whitespace collapsed.
+pub fn fold_case(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+        .replace('ß', "ss")
+        .replace('ς', "σ")
+}
+
+/// Returns every character whose name or one of whose aliases matches `name`.
+/// More than one result means the cue is ambiguous.
+pub fn find_characters<'a>(name: &str, characters: &'a [Character]) -> Vec<&'a Character> {
+    let folded = fold_case(name);
+    characters
+        .iter()
+        .filter(|c| {
+            fold_case(&c.name) == folded || c.aliases.iter().any(|alias| fold_case(alias) == folded)
+        })
+        .collect()
+}
+
+/// Explains why a line starting with `[` didn't match `[Name]: text`.
+fn diagnose_malformed_cue(line: &str, indent: usize) -> (Range<usize>, String) {
+    let content = line.trim_end();
+    let Some(close) = content[indent..].find(']').map(|i| indent + i) else {
+        return (
+            char_span(line, indent, content.len()),
+            "Speaker cue is missing its closing ']'".to_string(),
+        );
+    };
+    
+    let cue_span = char_span(line, indent, close + 1);
+    let name = content[indent + 1..close].trim();
+    
+    if name.is_empty() {
+        (cue_span, "Speaker name is empty".to_string())
+    } else {
+        (cue_span, format!("Missing ':' after [{}]", name))
+    }
+}
+
+/// Converts a byte range within `line` to character columns.
+fn char_span(line: &str, start: usize, end: usize) -> Range<usize> {
+    let start_col = line[..start].chars().count();
+    start_col..start_col + line[start..end].chars().count()
+}
+
+/// A pause directive. Captures: 1 = `beat`, 2 = length, 3 = unit.
+static PAUSE_PATTERN: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?i)^\{\s*(?:(beat)|(?:pause|silence)\s+(\d+(?:\.\d+)?)\s*(ms|s)?)\s*\}$").unwrap()
+});
+
+/// Parses `{pause 1.5s}`, `{pause 500ms}`, `{silence 2}` or `{beat}` into a length in seconds.
+/// A bare number is read as seconds.
+pub fn parse_pause_directive(directive: &str) -> Option<f32> {
+    let captures = PAUSE_PATTERN.captures(directive)?;
+    
+    if captures.get(1).is_some() {
+        return Some(BEAT_SECONDS);
+    }
+    
+    let value: f32 = captures.get(2)?.as_str().parse().ok()?;
+    match captures.get(3).map(|m| m.as_str().to_ascii_lowercase()) {
+        Some(unit) if unit == "ms" => Some(value / 1000.0),
+        _ => Some(value),
+    }
+}
+
+/// Splits a cue like `Gandalf|exag=0.8,cfg=0.3` into the character name and its overrides.
+/// Unknown keys and unparsable values are skipped and described in the returned problems.
+fn split_cue(cue: &str) -> (String, LineOverrides, Vec<String>) {
+    let mut overrides = LineOverrides::default();
+    let mut problems = Vec::new();
+    let (name, params) = match cue.split_once('|') {
+        Some((name, params)) => (name, params),
+        None => (cue, ""),
+    };
+    
+    for param in params.split(',').filter(|p| !p.trim().is_empty()) {
+        let Some((key, value)) = param.split_once('=') else {
+            problems.push(format!("Parameter '{}' should look like key=value", param.trim()));
+            continue;
+        };
+        let value = match value.trim().parse::<f32>() {
+            Ok(value) if value.is_finite() => value,
+            _ => {
+                problems.push(format!("'{}' is not a valid number for {}", value.trim(), key.trim()));
+                continue;
+            }
+        };
+        let slot = match key.trim().to_ascii_lowercase().as_str() {
+            "cfg" | "cfg_weight" => &mut overrides.cfg_weight,
+            "exag" | "exaggeration" => &mut overrides.exaggeration,
+            other => {
+                problems.push(format!("Unknown parameter '{}'; expected cfg or exag", other));
+                continue;
+            }
+        };
+        if !PARAMETER_RANGE.contains(&value) {
+            problems.push(format!(
+                "{} must be between {} and {}, not {}",
+                key.trim(),
+                PARAMETER_RANGE.start(),
+                PARAMETER_RANGE.end(),
+                value
             ));
+            continue;
         }
-        // Lines that don't match are silently ignored (as per spec)
+        *slot = Some(value);
+    }
+    
+    (name.trim().to_string(), overrides, problems)
+}
+
+/// Writes lines back out in the format `parse_script` reads.
+///
+/// Narration is written as an explicit cue for its narrator, so the text reads back
+/// the same whether or not the project voices unmarked lines. Later paragraphs of a
+/// line are indented after a blank line.
+pub fn format_script(lines: &[ScriptLine]) -> String {
+    let mut out: Vec<String> = Vec::new();
+    
+    for line in lines {
+        match &line.kind {
+            LineKind::Pause { seconds } => out.push(format!("{{pause {}s}}", seconds)),
+            LineKind::Scene { title } => {
+                if !out.is_empty() {
+                    out.push(String::new());
+                }
+                out.push(format!("## {}", title));
+            }
+            LineKind::Dialogue | LineKind::Narration => {
+                let paragraphs = line.paragraphs();
+                let first = paragraphs.first().copied().unwrap_or_default();
+                out.push(format!("[{}]: {}", format_cue(&line.character_name, &line.overrides), first));
+                for paragraph in paragraphs.iter().skip(1) {
+                    out.push(String::new());
+                    out.push(format!("    {}", paragraph));
+                }
+            }
+        }
+    }
+    
+    out.join("\n")
+}
+
+/// Writes a cue name with its overrides, e.g. `Gandalf|cfg=0.3,exag=0.8`.
+fn format_cue(name: &str, overrides: &LineOverrides) -> String {
+    let mut params = Vec::new();
+    if let Some(cfg) = overrides.cfg_weight {
+        params.push(format!("cfg={}", cfg));
+    }
+    if let Some(exag) = overrides.exaggeration {
+        params.push(format!("exag={}", exag));
     }
     
-    script_lines
+    if params.is_empty() {
+        name.to_string()
+    } else {
+        format!("{}|{}", name, params.join(","))
+    }
 }
 
 #[cfg(test)]
@@ -146,4 +726,424 @@ mod tests {
         
         assert_eq!(lines.len(), 0);
     }
+
+    #[test]
+    fn test_parse_narrates_unmarked_lines() {
+        let characters = vec![
+            Character::new("Gandalf".to_string()),
+            Character::new("Storyteller".to_string()),
+        ];
+        let options = ParseOptions {
+            unmarked_lines: UnmarkedLines::Narrate {
+                narrator_id: Some(characters[1].id.clone()),
+            },
+            ..Default::default()
+        };
+        
+        let script = "The bridge trembles.\n[Gandalf]: You cannot pass!\n\nThe Balrog falls.";
+        let lines = parse_script_with_options(script, &characters, &options).lines;
+        
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].kind, LineKind::Narration);
+        assert_eq!(lines[0].character_name, "Storyteller");
+        assert_eq!(lines[0].character_id, Some(characters[1].id.clone()));
+        assert_eq!(lines[0].text, "The bridge trembles.");
+        assert_eq!(lines[1].kind, LineKind::Dialogue);
+        assert_eq!(lines[2].text, "The Balrog falls.");
+    }
+
+    #[test]
+    fn test_parse_narration_without_bound_narrator() {
+        let characters = vec![Character::new("Gandalf".to_string())];
+        let options = ParseOptions {
+            unmarked_lines: UnmarkedLines::Narrate { narrator_id: Some("deleted".to_string()) },
+            ..Default::default()
+        };
+        
+        let result = parse_script_with_options("A stage direction", &characters, &options);
+        let lines = &result.lines;
+        
+        assert_eq!(lines.len(), 1);
+        assert_eq!(lines[0].character_name, DEFAULT_NARRATOR_NAME);
+        assert!(lines[0].character_id.is_none());
+        assert!(result.has_errors());
+    }
+
+    #[test]
+    fn test_options_from_config_respects_switch() {
+        let mut config = ProjectConfig {
+            narrator_character_id: Some("narrator".to_string()),
+            narrate_unmarked_lines: false,
+            ..Default::default()
+        };
+        assert_eq!(ParseOptions::from_config(&config).unmarked_lines, UnmarkedLines::Ignore);
+        
+        config.narrate_unmarked_lines = true;
+        assert_eq!(
+            ParseOptions::from_config(&config).unmarked_lines,
+            UnmarkedLines::Narrate { narrator_id: Some("narrator".to_string()) }
+        );
+    }
+
+    #[test]
+    fn test_parse_inline_overrides() {
+        let characters = vec![Character::new("Gandalf".to_string())];
+        
+        let script = "[Gandalf|exag=0.8,cfg=0.3]: You shall not pass!\n[Gandalf | cfg_weight = 0.2]: Fly";
+        let lines = parse_script(script, &characters);
+        
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].character_name, "Gandalf");
+        assert!(lines[0].character_id.is_some());
+        assert_eq!(lines[0].overrides.exaggeration, Some(0.8));
+        assert_eq!(lines[0].overrides.cfg_weight, Some(0.3));
+        assert_eq!(lines[1].overrides.cfg_weight, Some(0.2));
+        assert_eq!(lines[1].overrides.exaggeration, None);
+    }
+
+    #[test]
+    fn test_parse_skips_invalid_overrides() {
+        let characters = vec![Character::new("Gandalf".to_string())];
+        
+        let lines = parse_script("[Gandalf|exag=loud,speed=2]: Hello", &characters);
+        
+        assert_eq!(lines.len(), 1);
+        assert!(lines[0].overrides.is_empty());
+        
+        // Out-of-range values are dropped and reported before synthesis
+        let result = parse_script_with_options("[Gandalf|cfg=-3,exaggeration=50]: Hello", &characters, &ParseOptions::default());
+        assert!(result.lines[0].overrides.is_empty());
+        let messages: Vec<&str> = result.diagnostics.iter().map(|d| d.message.as_str()).collect();
+        assert_eq!(messages, ["cfg must be between 0 and 1, not -3", "exaggeration must be between 0 and 1, not 50"]);
+    }
+
+    #[test]
+    fn test_parse_pause_directives() {
+        let characters = vec![Character::new("Gandalf".to_string())];
+        
+        let script = "[Gandalf]: Run!\n{pause 1.5s}\n{ PAUSE 250ms }\n{silence 2}\n{beat}\n{unknown}\n[Gandalf]: Fly, you fools!";
+        let lines = parse_script(script, &characters);
+        
+        let kinds: Vec<LineKind> = lines.iter().map(|l| l.kind.clone()).collect();
+        assert_eq!(
+            kinds,
+            vec![
+                LineKind::Dialogue,
+                LineKind::Pause { seconds: 1.5 },
+                LineKind::Pause { seconds: 0.25 },
+                LineKind::Pause { seconds: 2.0 },
+                LineKind::Pause { seconds: BEAT_SECONDS },
+                LineKind::Dialogue,
+            ]
+        );
+        assert!(!lines[1].needs_synthesis());
+    }
+
+    #[test]
+    fn test_directives_are_not_narrated() {
+        let characters = vec![Character::new("Gandalf".to_string())];
+        let options = ParseOptions {
+            unmarked_lines: UnmarkedLines::Narrate { narrator_id: None },
+            ..Default::default()
+        };
+        
+        let result = parse_script_with_options("{beat}\n{not a directive}", &characters, &options);
+        
+        assert_eq!(result.lines.len(), 1);
+        assert_eq!(result.lines[0].kind, LineKind::Pause { seconds: BEAT_SECONDS });
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].severity, Severity::Warning);
+        assert_eq!(result.diagnostics[0].line, 2);
+    }
+
+    #[test]
+    fn test_diagnostics_for_malformed_cues() {
+        let characters = vec![Character::new("Gandalf".to_string())];
+        
+        let script = "[Gandalf] no colon\n  []: text\n[Gandalf\n[Gandalf]:\n[ ]: blank name";
+        let result = parse_script_with_options(script, &characters, &ParseOptions::default());
+        
+        assert!(result.lines.is_empty());
+        assert!(result.has_errors());
+        let summary: Vec<(usize, Range<usize>, Severity)> = result
+            .diagnostics
+            .iter()
+            .map(|d| (d.line, d.span.clone(), d.severity))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (1, 0..9, Severity::Error),
+                (2, 2..4, Severity::Error),
+                (3, 0..8, Severity::Error),
+                (4, 0..9, Severity::Warning),
+                (5, 0..3, Severity::Error),
+            ]
+        );
+        assert_eq!(result.diagnostics[0].message, "Missing ':' after [Gandalf]");
+    }
+
+    #[test]
+    fn test_diagnostics_for_unknown_character_and_parameters() {
+        let characters = vec![Character::new("Gandalf".to_string())];
+        
+        let script = "[Gandalf]: ok\n[Éowyn]: Who?\n[Gandalf|speed=2]: Fly";
+        let result = parse_script_with_options(script, &characters, &ParseOptions::default());
+        
+        assert_eq!(result.lines.len(), 3);
+        assert_eq!(result.diagnostics.len(), 2);
+        assert_eq!(result.diagnostics[0].line, 2);
+        assert_eq!(result.diagnostics[0].span, 0..7);
+        assert_eq!(result.diagnostics[0].severity, Severity::Error);
+        assert_eq!(result.diagnostics[1].line, 3);
+        assert_eq!(result.diagnostics[1].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_parse_unicode_case_folding() {
+        let characters = vec![
+            Character::new("Éowyn".to_string()),
+            Character::new("Straße".to_string()),
+        ];
+        
+        let script = "[ÉOWYN]: I am no man!\n[éowyn]: Look upon me\n[STRASSE]: Road";
+        let lines = parse_script(script, &characters);
+        
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].character_id, Some(characters[0].id.clone()));
+        assert_eq!(lines[1].character_id, Some(characters[0].id.clone()));
+        assert_eq!(lines[2].character_id, Some(characters[1].id.clone()));
+    }
+
+    #[test]
+    fn test_parse_matches_aliases() {
+        let mut gandalf = Character::new("Gandalf".to_string());
+        gandalf.aliases = vec!["Gandalf the Grey".to_string(), "GTG".to_string()];
+        let characters = vec![gandalf];
+        
+        let script = "[gandalf  the grey]: Fool of a Took!\n[gtg]: Run";
+        let lines = parse_script(script, &characters);
+        
+        assert_eq!(lines.len(), 2);
+        assert!(lines.iter().all(|l| l.character_id == Some(characters[0].id.clone())));
+    }
+
+    #[test]
+    fn test_parse_reports_ambiguous_alias() {
+        let mut merry = Character::new("Merry".to_string());
+        merry.aliases = vec!["Hobbit".to_string()];
+        let mut pippin = Character::new("Pippin".to_string());
+        pippin.aliases = vec!["hobbit".to_string()];
+        let characters = vec![merry, pippin];
+        
+        let result = parse_script_with_options("[HOBBIT]: Second breakfast?", &characters, &ParseOptions::default());
+        
+        assert_eq!(result.lines.len(), 1);
+        assert!(result.lines[0].character_id.is_none());
+        assert!(result.has_errors());
+        assert_eq!(result.diagnostics[0].message, "'HOBBIT' matches several characters: Merry, Pippin");
+    }
+
+    #[test]
+    fn test_parse_indented_continuation() {
+        let characters = vec![Character::new("Gandalf".to_string())];
+        
+        let script = "[Gandalf]: All we have to decide\n    is what to do with the time\n\n    that is given to us.\nNot a continuation";
+        let lines = parse_script(script, &characters);
+        
+        assert_eq!(lines.len(), 1);
+        assert_eq!(
+            lines[0].text,
+            "All we have to decide is what to do with the time\n\nthat is given to us."
+        );
+    }
+
+    #[test]
+    fn test_parse_block_cue_takes_following_prose() {
+        let characters = vec![
+            Character::new("Gandalf".to_string()),
+            Character::new("Frodo".to_string()),
+        ];
+        
+        let script = "[Gandalf]:\nThe Ring must be destroyed.\nIt cannot be hidden.\n\nOne of you must do this.\n[Frodo]: I will take it.";
+        let lines = parse_script(script, &characters);
+        
+        assert_eq!(lines.len(), 2);
+        assert_eq!(
+            lines[0].text,
+            "The Ring must be destroyed. It cannot be hidden.\n\nOne of you must do this."
+        );
+        assert_eq!(lines[1].text, "I will take it.");
+    }
+
+    #[test]
+    fn test_parse_ignores_unindented_prose_after_blank_line() {
+        let characters = vec![Character::new("Gandalf".to_string())];
+        
+        let script = "[Gandalf]: All we have to decide\n\nThe door creaks open.\nDust falls.\n\n[Gandalf]: Go.";
+        let lines = parse_script(script, &characters);
+        
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].text, "All we have to decide");
+        assert_eq!(lines[1].text, "Go.");
+    }
+
+    #[test]
+    fn test_parse_broken_cue_ends_block() {
+        let characters = vec![Character::new("Gandalf".to_string())];
+        
+        let script = "[Gandalf]:\nRun!\n[Gandalf Fly, you fools!\n[Gandalf]: [sigh] Gone.";
+        let result = parse_script_with_options(script, &characters, &ParseOptions::default());
+        
+        assert_eq!(result.lines.len(), 2);
+        assert_eq!(result.lines[0].text, "Run!");
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].line, 3);
+        assert_eq!(result.diagnostics[0].severity, Severity::Error);
+    }
+
+    #[test]
+    fn test_parse_continuation_ends_at_directive() {
+        let characters = vec![Character::new("Gandalf".to_string())];
+        let options = ParseOptions {
+            unmarked_lines: UnmarkedLines::Narrate { narrator_id: Some(characters[0].id.clone()) },
+            ..Default::default()
+        };
+        
+        let script = "[Gandalf]:\nRun!\n{beat}\nThe bridge falls.\n  Dust rises.";
+        let lines = parse_script_with_options(script, &characters, &options).lines;
+        
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0].text, "Run!");
+        assert_eq!(lines[1].kind, LineKind::Pause { seconds: BEAT_SECONDS });
+        assert_eq!(lines[2].kind, LineKind::Narration);
+        assert_eq!(lines[2].text, "The bridge falls. Dust rises.");
+    }
+
+    #[test]
+    fn test_scene_headings() {
+        let characters = vec![Character::new("Gandalf".to_string())];
+        
+        let result = parse_script_with_options(
+            "## Moria\n[Gandalf]: Fly, you fools!\n    Run!\n##\n[Gandalf]: Gone.",
+            &characters,
+            &ParseOptions::default(),
+        );
+        
+        assert_eq!(result.lines.len(), 3);
+        assert_eq!(result.lines[0].kind, LineKind::Scene { title: "Moria".to_string() });
+        assert!(!result.lines[0].needs_synthesis());
+        // A heading ends the previous block even when indented lines follow it
+        assert_eq!(result.lines[1].text, "Fly, you fools! Run!");
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].line, 4);
+        assert_eq!(result.diagnostics[0].severity, Severity::Warning);
+    }
+
+    #[test]
+    fn test_format_script_round_trip() {
+        let characters = vec![
+            Character::new("Gandalf".to_string()),
+            Character::new("Frodo".to_string()),
+        ];
+        let script = "## Bag End\n[Gandalf|cfg=0.3,exag=0.8]: Keep it secret.\n\n    Keep it safe.\n{pause 1.5s}\n[Frodo]: I will.";
+        let lines = parse_script(script, &characters);
+        
+        let formatted = format_script(&lines);
+        assert_eq!(formatted, script);
+        
+        let reparsed = parse_script(&formatted, &characters);
+        assert_eq!(reparsed.len(), lines.len());
+        assert_eq!(reparsed[1].text, lines[1].text);
+        assert_eq!(reparsed[1].overrides, lines[1].overrides);
+    }
+
+    #[test]
+    fn test_variables_resolved_before_parsing() {
+        let characters = vec![Character::new("Vex".to_string())];
+        let mut options = ParseOptions::default();
+        options.variables.insert("hero".to_string(), "Vex".to_string());
+        options.variables.insert("campaign".to_string(), "Critical Role".to_string());
+        
+        let result = parse_script_with_options(
+            "[{{hero}}]: Previously, on {{ campaign }}...\n[Vex]: Hi {{rogue}}!",
+            &characters,
+            &options,
+        );
+        
+        assert_eq!(result.lines.len(), 2);
+        assert_eq!(result.lines[0].character_name, "Vex");
+        assert!(result.lines[0].character_id.is_some());
+        assert_eq!(result.lines[0].text, "Previously, on Critical Role...");
+        assert_eq!(result.lines[1].text, "Hi {{rogue}}!");
+        
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].severity, Severity::Error);
+        assert_eq!(result.diagnostics[0].line, 2);
+        assert_eq!(result.diagnostics[0].span, 10..19);
+        assert!(result.diagnostics[0].message.contains("{{rogue}}"));
+        
+        let used = referenced_variables("{{hero}} {{rogue}}", &options.variables);
+        assert_eq!(used.into_iter().collect::<Vec<_>>(), vec![("hero".to_string(), "Vex".to_string())]);
+    }
+
+    #[test]
+    fn test_spans_map_back_through_variables() {
+        let mut options = ParseOptions::default();
+        options.variables.insert("who".to_string(), "Grog Strongjaw".to_string());
+        
+        // The unknown speaker is reported over the placeholder as written
+        let result = parse_script_with_options("[{{who}}]: Hm.", &[], &options);
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].span, 0..9);
+        
+        let result = parse_script_with_options("{{who}} [Bad: text", &[], &options);
+        assert_eq!(result.diagnostics.len(), 0);
+        let result = parse_script_with_options("  [{{who}}] no colon", &[], &options);
+        assert_eq!(result.diagnostics[0].span, 2..11);
+    }
+
+    #[test]
+    fn test_unknown_tags_get_suggestions() {
+        let characters = vec![Character::new("Gandalf".to_string())];
+        
+        let result = parse_script_with_options(
+            "[Gandalf]: [sigh] Fine. [laughs] [applause]",
+            &characters,
+            &ParseOptions::default(),
+        );
+        
+        assert_eq!(result.lines.len(), 1);
+        assert_eq!(result.diagnostics.len(), 2);
+        assert_eq!(result.diagnostics[0].severity, Severity::Warning);
+        assert_eq!(result.diagnostics[0].span, 24..32);
+        assert_eq!(result.diagnostics[0].message, "Unknown tag [laughs]; did you mean [laugh]?");
+        assert!(result.diagnostics[1].message.starts_with("Unknown tag [applause]; known tags are [clear throat]"));
+    }
+
+    #[test]
+    fn test_tags_are_not_speakers() {
+        let characters = vec![Character::new("Gandalf".to_string())];
+        
+        // A line opening with a tag continues the previous speaker
+        let result = parse_script_with_options(
+            "[Gandalf]: Well.\n[sigh] So it begins.",
+            &characters,
+            &ParseOptions::default(),
+        );
+        assert!(result.diagnostics.is_empty());
+        assert_eq!(result.lines.len(), 1);
+        assert_eq!(result.lines[0].text, "Well. [sigh] So it begins.");
+        
+        // A tag written as a cue is explained rather than treated as a character
+        let result = parse_script_with_options("[sigh]: So it begins.", &characters, &ParseOptions::default());
+        assert_eq!(result.diagnostics.len(), 1);
+        assert_eq!(result.diagnostics[0].severity, Severity::Error);
+        assert!(result.diagnostics[0].message.starts_with("[sigh] is a tag, not a speaker"));
+        
+        // With nothing to attach to, the tag line is ignored with a warning
+        let result = parse_script_with_options("[sigh] So it begins.", &characters, &ParseOptions::default());
+        assert!(result.lines.is_empty());
+        assert_eq!(result.diagnostics[0].severity, Severity::Warning);
+    }
 }
@@ -0,0 +1,199 @@
+use crate::models::script::{LineKind, ScriptLine, PARAGRAPH_SEPARATOR};
+use crate::utils::audio::wav_duration;
+use regex::Regex;
+use std::path::{Path, PathBuf};
+
+/// One timed caption.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtitleCue {
+    /// Start time in seconds from the beginning of the exported audio.
+    pub start: f32,
+    /// End time in seconds.
+    pub end: f32,
+    pub speaker: String,
+    pub text: String,
+}
+
+/// Times each synthesized line against the combined export.
+///
+/// Cues follow each other the way `combine_segments` lays out the audio: every
+/// clip starts where the previous clip or pause ended, and lasts exactly as long
+/// as its WAV file. Paralinguistic tags like `[sigh]` are left out of the caption
+/// text; lines that are nothing but tags still take up time but get no cue.
+pub fn build_cues(lines: &[ScriptLine]) -> Result<Vec<SubtitleCue>, String> {
+    let tag_pattern = Regex::new(r"\[[^\]]*\]").unwrap();
+    let mut cues = Vec::new();
+    let mut position = 0.0f32;
+
+    for line in lines {
+        match &line.kind {
+            LineKind::Pause { seconds } => position += seconds,
+            LineKind::Scene { .. } => {}
+            LineKind::Dialogue | LineKind::Narration => {
+                let Some(output_path) = &line.output_path else {
+                    continue;
+                };
+                let duration = wav_duration(Path::new(output_path))?;
+                let text = tag_pattern
+                    .replace_all(&line.text.replace(PARAGRAPH_SEPARATOR, " "), "")
+                    .split_whitespace()
+                    .collect::<Vec<_>>()
+                    .join(" ");
+
+                if !text.is_empty() {
+                    cues.push(SubtitleCue {
+                        start: position,
+                        end: position + duration,
+                        speaker: line.character_name.clone(),
+                        text,
+                    });
+                }
+                position += duration;
+            }
+        }
+    }
+
+    Ok(cues)
+}
+
+/// Formats cues as SubRip (`.srt`).
+pub fn to_srt(cues: &[SubtitleCue]) -> String {
+    cues.iter()
+        .enumerate()
+        .map(|(index, cue)| {
+            format!(
+                "{}\n{} --> {}\n{}: {}\n",
+                index + 1,
+                format_timestamp(cue.start, ','),
+                format_timestamp(cue.end, ','),
+                cue.speaker,
+                cue.text
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Formats cues as WebVTT (`.vtt`).
+pub fn to_vtt(cues: &[SubtitleCue]) -> String {
+    let mut out = String::from("WEBVTT\n");
+    for cue in cues {
+        out.push_str(&format!(
+            "\n{} --> {}\n{}: {}\n",
+            format_timestamp(cue.start, '.'),
+            format_timestamp(cue.end, '.'),
+            cue.speaker,
+            cue.text
+        ));
+    }
+    out
+}
+
+/// Writes `.srt` and `.vtt` files next to an exported WAV, sharing its file stem.
+/// Returns the paths written.
+pub fn write_subtitles(lines: &[ScriptLine], wav_path: &Path) -> Result<Vec<PathBuf>, String> {
+    let cues = build_cues(lines)?;
+    let outputs = [
+        (wav_path.with_extension("srt"), to_srt(&cues)),
+        (wav_path.with_extension("vtt"), to_vtt(&cues)),
+    ];
+
+    let mut written = Vec::new();
+    for (path, contents) in outputs {
+        std::fs::write(&path, contents)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        written.push(path);
+    }
+    Ok(written)
+}
+
+/// Formats seconds as `HH:MM:SS,mmm`, with `separator` before the milliseconds.
+fn format_timestamp(seconds: f32, separator: char) -> String {
+    let total_ms = (seconds.max(0.0) as f64 * 1000.0).round() as u64;
+    format!(
+        "{:02}:{:02}:{:02}{}{:03}",
+        total_ms / 3_600_000,
+        total_ms / 60_000 % 60,
+        total_ms / 1000 % 60,
+        separator,
+        total_ms % 1000
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use uuid::Uuid;
+
+    fn write_clip(path: &Path, frames: usize) {
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 1000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).unwrap();
+        for _ in 0..frames {
+            writer.write_sample(0i16).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
+
+    fn synthesized(name: &str, text: &str, path: &Path) -> ScriptLine {
+        let mut line = ScriptLine::new(name.to_string(), text.to_string(), None);
+        line.output_path = Some(path.to_string_lossy().to_string());
+        line
+    }
+
+    #[test]
+    fn test_cues_follow_clips_and_pauses() {
+        let dir = std::env::temp_dir().join(format!("test_subtitles_{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let first = dir.join("a.wav");
+        let second = dir.join("b.wav");
+        let third = dir.join("c.wav");
+        write_clip(&first, 1500);
+        write_clip(&second, 2000);
+        write_clip(&third, 500);
+
+        let lines = vec![
+            ScriptLine::scene("Moria".to_string()),
+            synthesized("Gandalf", "[sigh] Fly,\n\nyou fools!", &first),
+            ScriptLine::pause(0.5),
+            synthesized("Frodo", "Gandalf!", &second),
+            synthesized("Frodo", "[gasp]", &third),
+            synthesized("Sam", "Come on.", &first),
+        ];
+
+        let cues = build_cues(&lines).unwrap();
+        assert_eq!(cues.len(), 3);
+        assert_eq!((cues[0].start, cues[0].end), (0.0, 1.5));
+        assert_eq!(cues[0].text, "Fly, you fools!");
+        assert_eq!((cues[1].start, cues[1].end), (2.0, 4.0));
+        // The tag-only line still takes up its time
+        assert_eq!((cues[2].start, cues[2].end), (4.5, 6.0));
+
+        let written = write_subtitles(&lines, &dir.join("export.wav")).unwrap();
+        assert_eq!(written, vec![dir.join("export.srt"), dir.join("export.vtt")]);
+
+        let _ = fs::remove_dir_all(dir);
+    }
+
+    #[test]
+    fn test_srt_and_vtt_formatting() {
+        let cues = vec![
+            SubtitleCue { start: 0.0, end: 1.5, speaker: "Gandalf".to_string(), text: "Fly!".to_string() },
+            SubtitleCue { start: 3661.25, end: 3662.0, speaker: "Frodo".to_string(), text: "No!".to_string() },
+        ];
+
+        assert_eq!(
+            to_srt(&cues),
+            "1\n00:00:00,000 --> 00:00:01,500\nGandalf: Fly!\n\n2\n01:01:01,250 --> 01:01:02,000\nFrodo: No!\n"
+        );
+        assert_eq!(
+            to_vtt(&cues),
+            "WEBVTT\n\n00:00:00.000 --> 00:00:01.500\nGandalf: Fly!\n\n01:01:01.250 --> 01:01:02.000\nFrodo: No!\n"
+        );
+    }
+}
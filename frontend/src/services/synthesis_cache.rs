@@ -0,0 +1,194 @@
+use crate::models::script::SynthesisParams;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+/// Name of the cache folder inside the output directory.
+pub const CACHE_DIR_NAME: &str = ".cache";
+
+/// Content-addressed store of synthesized clips.
+///
+/// Each clip is stored as `<key>.wav`, where the key hashes everything that
+/// determines the audio. A clip's modification time records its last use, and the
+/// least recently used clips are evicted once the cache grows past `max_bytes`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SynthesisCache {
+    dir: PathBuf,
+    max_bytes: u64,
+}
+
+impl SynthesisCache {
+    pub fn new(dir: PathBuf, max_bytes: u64) -> Self {
+        Self { dir, max_bytes }
+    }
+
+    /// The cache kept under an output directory, limited to `limit_mb` megabytes.
+    pub fn in_output_dir(output_dir: &Path, limit_mb: u64) -> Self {
+        Self::new(output_dir.join(CACHE_DIR_NAME), limit_mb * 1024 * 1024)
+    }
+
+    /// Key for one synthesis request. `reference_digest` comes from `file_digest`
+    /// on the voice reference, so a re-recorded reference misses the cache.
+    pub fn key(text: &str, reference_digest: &str, params: &SynthesisParams) -> String {
+        let mut hasher = Sha256::new();
+        // Length-prefix the strings so fields can't run into each other
+        for field in [text, reference_digest, &params.model] {
+            hasher.update((field.len() as u64).to_le_bytes());
+            hasher.update(field.as_bytes());
+        }
+        hasher.update(params.cfg_weight.to_bits().to_le_bytes());
+        hasher.update(params.exaggeration.to_bits().to_le_bytes());
+        match params.seed {
+            Some(seed) => {
+                hasher.update([1]);
+                hasher.update(seed.to_le_bytes());
+            }
+            None => hasher.update([0]),
+        }
+        hex(&hasher.finalize())
+    }
+
+    /// Copies the clip for `key` to `dest` and marks it used. Returns whether it was cached.
+    pub fn get(&self, key: &str, dest: &Path) -> bool {
+        let path = self.entry_path(key);
+        if fs::copy(&path, dest).is_err() {
+            return false;
+        }
+        // Refresh the timestamp so eviction sees this clip as recently used
+        if let Ok(file) = fs::File::options().append(true).open(&path) {
+            let _ = file.set_modified(SystemTime::now());
+        }
+        true
+    }
+
+    /// Stores a copy of `source` under `key`, then evicts old clips over the limit.
+    pub fn put(&self, key: &str, source: &Path) -> Result<(), String> {
+        fs::create_dir_all(&self.dir)
+            .map_err(|e| format!("Failed to create cache directory {}: {}", self.dir.display(), e))?;
+        fs::copy(source, self.entry_path(key)).map_err(|e| format!("Failed to cache clip: {}", e))?;
+        self.evict()
+    }
+
+    /// Removes least recently used clips until the cache fits its limit.
+    pub fn evict(&self) -> Result<(), String> {
+        let mut entries = self.entries();
+        let mut total: u64 = entries.iter().map(|(_, size, _)| size).sum();
+        entries.sort_by_key(|(_, _, used)| *used);
+
+        for (path, size, _) in entries {
+            if total <= self.max_bytes {
+                break;
+            }
+            fs::remove_file(&path).map_err(|e| format!("Failed to evict {}: {}", path.display(), e))?;
+            total -= size;
+        }
+        Ok(())
+    }
+
+    /// Deletes every cached clip.
+    pub fn clear(&self) -> Result<(), String> {
+        for (path, _, _) in self.entries() {
+            fs::remove_file(&path).map_err(|e| format!("Failed to remove {}: {}", path.display(), e))?;
+        }
+        Ok(())
+    }
+
+    /// Number of cached clips and their total size in bytes.
+    pub fn usage(&self) -> (usize, u64) {
+        let entries = self.entries();
+        (entries.len(), entries.iter().map(|(_, size, _)| size).sum())
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{}.wav", key))
+    }
+
+    /// Cached clips as (path, size, last used).
+    fn entries(&self) -> Vec<(PathBuf, u64, SystemTime)> {
+        let Ok(read_dir) = fs::read_dir(&self.dir) else {
+            return Vec::new();
+        };
+        read_dir
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().extension().is_some_and(|ext| ext == "wav"))
+            .filter_map(|entry| {
+                let metadata = entry.metadata().ok()?;
+                let used = metadata.modified().unwrap_or(SystemTime::UNIX_EPOCH);
+                Some((entry.path(), metadata.len(), used))
+            })
+            .collect()
+    }
+}
+
+/// SHA-256 of a file's contents, as hex.
+pub fn file_digest(path: &Path) -> Result<String, String> {
+    let bytes = fs::read(path).map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+    Ok(hex(&Sha256::digest(&bytes)))
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use uuid::Uuid;
+
+    fn params(cfg_weight: f32, exaggeration: f32, model: &str, seed: Option<u64>) -> SynthesisParams {
+        SynthesisParams { cfg_weight, exaggeration, model: model.to_string(), seed }
+    }
+
+    #[test]
+    fn test_key_covers_every_input() {
+        let base = params(0.5, 0.5, "turbo", None);
+        let key = SynthesisCache::key("Hello.", "abc", &base);
+
+        assert_eq!(key, SynthesisCache::key("Hello.", "abc", &base));
+        assert_eq!(key.len(), 64);
+        assert_ne!(key, SynthesisCache::key("Hello!", "abc", &base));
+        assert_ne!(key, SynthesisCache::key("Hello.", "abd", &base));
+        assert_ne!(key, SynthesisCache::key("Hello.", "abc", &params(0.6, 0.5, "turbo", None)));
+        assert_ne!(key, SynthesisCache::key("Hello.", "abc", &params(0.5, 0.6, "turbo", None)));
+        assert_ne!(key, SynthesisCache::key("Hello.", "abc", &params(0.5, 0.5, "standard", None)));
+        assert_ne!(key, SynthesisCache::key("Hello.", "abc", &params(0.5, 0.5, "turbo", Some(0))));
+        let empty = params(0.5, 0.5, "", None);
+        assert_ne!(SynthesisCache::key("ab", "c", &empty), SynthesisCache::key("a", "bc", &empty));
+    }
+
+    #[test]
+    fn test_get_put_and_lru_eviction() {
+        let dir = std::env::temp_dir().join(format!("test_cache_{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let clip = dir.join("clip.wav");
+        fs::write(&clip, vec![0u8; 100]).unwrap();
+        let cache = SynthesisCache::new(dir.join("cache"), 250);
+        let dest = dir.join("out.wav");
+
+        assert!(!cache.get("a", &dest));
+        cache.put("a", &clip).unwrap();
+        cache.put("b", &clip).unwrap();
+        assert_eq!(cache.usage(), (2, 200));
+
+        // Use `a` so `b` becomes the least recently used
+        let old = SystemTime::now() - Duration::from_secs(60);
+        let b = fs::File::options().append(true).open(cache.entry_path("b")).unwrap();
+        b.set_modified(old).unwrap();
+        let a = fs::File::options().append(true).open(cache.entry_path("a")).unwrap();
+        a.set_modified(old - Duration::from_secs(60)).unwrap();
+        assert!(cache.get("a", &dest));
+        assert_eq!(fs::read(&dest).unwrap().len(), 100);
+
+        cache.put("c", &clip).unwrap();
+        assert!(!cache.entry_path("b").exists());
+        assert!(cache.entry_path("a").exists());
+        assert_eq!(cache.usage().0, 2);
+
+        cache.clear().unwrap();
+        assert_eq!(cache.usage(), (0, 0));
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
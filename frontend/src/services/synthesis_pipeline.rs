@@ -0,0 +1,921 @@
+use crate::models::character::Character;
+use crate::models::manifest::{ManifestLine, ManifestTake, RenderManifest, MANIFEST_FILE_NAME};
+use crate::models::project::ProjectConfig;
+use crate::models::script::{LineKind, ScriptLine, SynthesisParams, SynthesisStatus, Take, PARAGRAPH_PAUSE_SECONDS};
+use crate::models::settings::Settings;
+use crate::services::api::{is_transient_error, synthesize_audio, SYNTHESIS_MODEL};
+use crate::services::pronunciation::apply_pronunciations;
+use crate::services::run_control::{RunControl, RunState};
+use crate::services::synthesis_cache::{file_digest, SynthesisCache};
+use crate::services::text_chunker::chunk_text;
+use crate::services::text_normalizer::normalize_text;
+use crate::utils::audio::{combine_segments, wav_duration, AudioSegment};
+use chrono::{DateTime, Local, Utc};
+use std::collections::{HashMap, VecDeque};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+use tokio::sync::mpsc;
+use tokio::task::JoinSet;
+
+/// Wait before the first retry of a transient synthesis failure; doubled each retry.
+const RETRY_BASE_DELAY: Duration = Duration::from_secs(1);
+
+/// Progress reported by a running pipeline, in the order it happens.
+#[derive(Debug, Clone, PartialEq)]
+pub enum PipelineEvent {
+    /// These lines are waiting to be synthesized.
+    Queued { line_ids: Vec<String> },
+    Started { index: usize, line_id: String },
+    /// The line's new takes, at least one. The first becomes its output.
+    Done { index: usize, line_id: String, takes: Vec<Take> },
+    Failed { index: usize, line_id: String, reason: String },
+    /// The run is over. Always the last event.
+    Finished(RunOutcome),
+}
+
+/// How a run ended.
+#[derive(Debug, Clone, PartialEq)]
+pub enum RunOutcome {
+    Completed,
+    /// Every line was tried, but this many still failed after retries.
+    CompletedWithFailures(usize),
+    /// The run stopped at the first line that failed.
+    Stopped(String),
+    Cancelled,
+}
+
+impl std::fmt::Display for RunOutcome {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RunOutcome::Completed => write!(f, "completed"),
+            RunOutcome::CompletedWithFailures(failed) => write!(f, "completed with {} failed line(s)", failed),
+            RunOutcome::Stopped(error) => write!(f, "stopped: {}", error),
+            RunOutcome::Cancelled => write!(f, "cancelled"),
+        }
+    }
+}
+
+/// One synthesis run over a script, independent of any component.
+///
+/// The pipeline renders the pending lines of a range (see `pending_lines`) or an
+/// exact set of lines, up to `Settings::max_concurrent_jobs` at once, and reports
+/// each step as a `PipelineEvent`. It owns everything it needs, so a run keeps going
+/// when the view that started it goes away.
+///
+/// Each run writes its clips to a directory of its own (see `run_dir`), so runs of
+/// other scripts or projects never overwrite them, and records how every clip was
+/// made in a `manifest.json` there.
+#[derive(Debug, Clone)]
+pub struct SynthesisPipeline {
+    lines: Vec<ScriptLine>,
+    pending: Vec<String>,
+    characters: Vec<Character>,
+    settings: Settings,
+    project: ProjectConfig,
+    cfg_weight: f32,
+    exaggeration: f32,
+    takes: usize,
+    run_dir: PathBuf,
+}
+
+impl SynthesisPipeline {
+    /// A pipeline for the lines in `range` that still need audio, synthesized with
+    /// the default parameters. Creates the run's directory, failing if it can't.
+    pub fn new(
+        lines: Vec<ScriptLine>,
+        range: Range<usize>,
+        characters: Vec<Character>,
+        settings: Settings,
+        project: ProjectConfig,
+    ) -> Result<Self, String> {
+        let pending = pending_lines(&lines, range);
+        Self::for_lines(lines, &pending, characters, settings, project)
+    }
+
+    /// A pipeline for exactly the lines with these IDs, whether or not they already
+    /// have audio, synthesized with the default parameters. Creates the run's
+    /// directory, failing if it can't.
+    pub fn for_lines(
+        lines: Vec<ScriptLine>,
+        line_ids: &[String],
+        characters: Vec<Character>,
+        settings: Settings,
+        project: ProjectConfig,
+    ) -> Result<Self, String> {
+        let pending = lines
+            .iter()
+            .filter(|line| line.needs_synthesis() && line_ids.contains(&line.id))
+            .map(|line| line.id.clone())
+            .collect();
+        let run_dir = run_directory(
+            Path::new(&settings.output_directory),
+            settings.active_project_id.as_deref(),
+            Local::now(),
+        )?;
+        Ok(Self {
+            lines,
+            pending,
+            characters,
+            settings,
+            project,
+            cfg_weight: 0.5,
+            exaggeration: 0.5,
+            takes: 1,
+            run_dir,
+        })
+    }
+
+    /// Sets the parameters for lines without inline overrides or character defaults.
+    pub fn with_parameters(mut self, cfg_weight: f32, exaggeration: f32) -> Self {
+        self.cfg_weight = cfg_weight;
+        self.exaggeration = exaggeration;
+        self
+    }
+
+    /// Sets how many takes are synthesized for each line; at least one.
+    pub fn with_takes(mut self, takes: usize) -> Self {
+        self.takes = takes.max(1);
+        self
+    }
+
+    /// IDs of the lines this run will synthesize, in script order.
+    pub fn pending(&self) -> &[String] {
+        &self.pending
+    }
+
+    /// Where this run writes its clips and manifest.
+    pub fn run_dir(&self) -> &Path {
+        &self.run_dir
+    }
+
+    /// Starts the run on the Tokio runtime and returns its events.
+    pub fn spawn(self, control: RunControl) -> mpsc::UnboundedReceiver<PipelineEvent> {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        tokio::spawn(self.run(control, sender));
+        receiver
+    }
+
+    /// Runs to the end, sending events to `events`. A dropped receiver doesn't
+    /// stop the run; cancel it with `control` instead.
+    pub async fn run(self, control: RunControl, events: mpsc::UnboundedSender<PipelineEvent>) -> RunOutcome {
+        let mut manifest = RenderManifest {
+            project_id: self.settings.active_project_id.clone(),
+            started_at: Utc::now(),
+            finished_at: None,
+            outcome: String::new(),
+            lines: Vec::new(),
+        };
+        let outcome = self.run_lines(&control, &events, &mut manifest.lines).await;
+
+        manifest.finished_at = Some(Utc::now());
+        manifest.outcome = outcome.to_string();
+        manifest.lines.sort_by_key(|line| line.index);
+        if let Err(e) = self.write_manifest(&manifest) {
+            eprintln!("   ⚠️ {}", e);
+        }
+
+        let _ = events.send(PipelineEvent::Finished(outcome.clone()));
+        outcome
+    }
+
+    fn write_manifest(&self, manifest: &RenderManifest) -> Result<(), String> {
+        let path = self.run_dir.join(MANIFEST_FILE_NAME);
+        let json = serde_json::to_string_pretty(manifest).map_err(|e| format!("Failed to serialize manifest: {}", e))?;
+        std::fs::write(&path, json).map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Synthesizes the pending lines, adding an entry to `manifest` for each line
+    /// that finishes or fails.
+    async fn run_lines(
+        &self,
+        control: &RunControl,
+        events: &mpsc::UnboundedSender<PipelineEvent>,
+        manifest: &mut Vec<ManifestLine>,
+    ) -> RunOutcome {
+        let send = |event: PipelineEvent| {
+            let _ = events.send(event);
+        };
+
+        let output_dir = self.run_dir.clone();
+        // Manifest entries of the lines in flight, and reference digests by path
+        let mut in_flight: HashMap<usize, ManifestLine> = HashMap::new();
+        let mut digests: HashMap<String, Option<String>> = HashMap::new();
+
+        // Queue the pending lines, then dispatch them in line order, keeping at
+        // most `max_concurrent_jobs` requests in flight
+        send(PipelineEvent::Queued { line_ids: self.pending.clone() });
+        let max_jobs = self.settings.max_concurrent_jobs.max(1);
+
+        let mut queue: VecDeque<usize> = (0..self.lines.len())
+            .filter(|&index| self.pending.contains(&self.lines[index].id))
+            .collect();
+        let mut jobs = JoinSet::new();
+        // The line each job renders, so a job that panics is still reported for its line
+        let mut job_lines: HashMap<tokio::task::Id, usize> = HashMap::new();
+        let mut failure: Option<String> = None;
+        let mut failed = 0;
+
+        loop {
+            // Fill the free slots while running, unless a line has already failed
+            while failure.is_none() && control.state() == RunState::Running && jobs.len() < max_jobs {
+                let Some(index) = queue.pop_front() else {
+                    break;
+                };
+                let line = &self.lines[index];
+
+                let voice_path = match voice_reference(line, &self.characters) {
+                    Ok(path) => path,
+                    Err((reason, error_msg)) => {
+                        let mut entry = manifest_line(index, line, None, None);
+                        entry.error = Some(reason.clone());
+                        manifest.push(entry);
+                        send(PipelineEvent::Failed { index, line_id: line.id.clone(), reason });
+                        failed += 1;
+                        if !self.settings.continue_on_error {
+                            failure = Some(error_msg);
+                        }
+                        continue;
+                    }
+                };
+
+                send(PipelineEvent::Started { index, line_id: line.id.clone() });
+                let digest = digests
+                    .entry(voice_path.clone())
+                    .or_insert_with(|| file_digest(Path::new(&voice_path)).ok())
+                    .clone();
+                in_flight.insert(index, manifest_line(index, line, Some(voice_path.clone()), digest));
+
+                let params = resolve_params(line, &self.characters, self.cfg_weight, self.exaggeration);
+                // New takes are numbered after the ones the line already has
+                let takes = line.takes.len()..line.takes.len() + self.takes;
+                let (line, settings, project) = (line.clone(), self.settings.clone(), self.project.clone());
+                let output_dir = output_dir.clone();
+
+                let job = jobs.spawn(async move {
+                    let result = synthesize_takes(&line, &voice_path, &output_dir, &params, takes, &settings, &project).await;
+                    (line.id, result)
+                });
+                job_lines.insert(job.id(), index);
+            }
+
+            if jobs.is_empty() {
+                // Nothing in flight: the run is over, or paused between lines
+                if failure.is_some() || queue.is_empty() || control.is_cancelled() {
+                    break;
+                }
+                if control.wait_while_paused().await == RunState::Cancelled {
+                    break;
+                }
+                continue;
+            }
+
+            // Cancelling aborts the requests still in flight
+            let joined = tokio::select! {
+                joined = jobs.join_next_with_id() => joined,
+                _ = control.cancelled() => {
+                    jobs.abort_all();
+                    break;
+                }
+            };
+            let Some(joined) = joined else {
+                break;
+            };
+            let (index, line_id, result) = match joined {
+                Ok((id, (line_id, result))) => (job_lines.remove(&id).unwrap(), line_id, result),
+                Err(e) => {
+                    let index = job_lines.remove(&e.id()).unwrap();
+                    (index, self.lines[index].id.clone(), Err(format!("Synthesis job failed: {}", e)))
+                }
+            };
+            match result {
+                Ok(rendered) => {
+                    let (takes, records): (Vec<Take>, Vec<ManifestTake>) = rendered.into_iter().unzip();
+                    if let Some(mut entry) = in_flight.remove(&index) {
+                        entry.takes = records;
+                        manifest.push(entry);
+                    }
+                    send(PipelineEvent::Done { index, line_id, takes });
+                }
+                Err(reason) => {
+                    let error_msg = format!("Failed to synthesize line {}: {}", index + 1, reason);
+                    if let Some(mut entry) = in_flight.remove(&index) {
+                        entry.error = Some(reason.clone());
+                        manifest.push(entry);
+                    }
+                    send(PipelineEvent::Failed { index, line_id, reason });
+                    failed += 1;
+                    if !self.settings.continue_on_error {
+                        failure.get_or_insert(error_msg);
+                    }
+                }
+            }
+        }
+
+        if control.is_cancelled() {
+            RunOutcome::Cancelled
+        } else if let Some(error_msg) = failure {
+            RunOutcome::Stopped(error_msg)
+        } else if failed > 0 {
+            RunOutcome::CompletedWithFailures(failed)
+        } else {
+            RunOutcome::Completed
+        }
+    }
+}
+
+/// A subscriber's view of the latest run, kept up to date with `apply`.
+#[derive(Debug, Clone, Default)]
+pub struct RunStatus {
+    /// Pauses and cancels the run; present while it is going.
+    pub control: Option<RunControl>,
+    pub paused: bool,
+    /// Index of the line started most recently.
+    pub current_index: usize,
+    /// Why the last run didn't complete cleanly, or why it couldn't start.
+    pub error: Option<String>,
+    /// Status of each queued line before the run, restored if it doesn't finish.
+    previous_statuses: HashMap<String, SynthesisStatus>,
+}
+
+impl RunStatus {
+    /// Status for a run that was just started with `control`.
+    pub fn started(control: RunControl, first_index: usize) -> Self {
+        Self {
+            control: Some(control),
+            current_index: first_index,
+            ..Self::default()
+        }
+    }
+
+    /// Status for a run that couldn't start.
+    pub fn failed(error: String) -> Self {
+        Self {
+            error: Some(error),
+            ..Self::default()
+        }
+    }
+
+    pub fn is_running(&self) -> bool {
+        self.control.is_some()
+    }
+
+    /// Records an event in this status and in the script's lines. Lines are found
+    /// by ID, so edits made while the run is going don't misplace results.
+    pub fn apply(&mut self, event: &PipelineEvent, lines: &mut [ScriptLine]) {
+        let mut set_status = |line_id: &str, status: SynthesisStatus| {
+            if let Some(line) = lines.iter_mut().find(|l| l.id == line_id) {
+                line.status = status;
+            }
+        };
+
+        match event {
+            PipelineEvent::Queued { line_ids } => {
+                for line in lines.iter_mut().filter(|l| line_ids.contains(&l.id)) {
+                    let previous = std::mem::replace(&mut line.status, SynthesisStatus::Queued);
+                    self.previous_statuses.insert(line.id.clone(), previous);
+                }
+            }
+            PipelineEvent::Started { index, line_id } => {
+                self.current_index = *index;
+                set_status(line_id, SynthesisStatus::Working);
+            }
+            PipelineEvent::Done { line_id, takes, .. } => {
+                if let Some(line) = lines.iter_mut().find(|l| &l.id == line_id) {
+                    let first_new = line.takes.len();
+                    line.takes.extend(takes.iter().cloned());
+                    line.select_take(first_new);
+                    line.take_error = None;
+                }
+            }
+            PipelineEvent::Failed { line_id, reason, .. } => {
+                if let Some(line) = lines.iter_mut().find(|l| &l.id == line_id) {
+                    // A done line keeps its selected take; only the new take failed
+                    let was_done = self.previous_statuses.get(line_id) == Some(&SynthesisStatus::Done);
+                    if was_done && line.selected_take().is_some() {
+                        line.status = SynthesisStatus::Done;
+                        line.take_error = Some(reason.clone());
+                    } else {
+                        line.status = SynthesisStatus::Error(reason.clone());
+                    }
+                }
+            }
+            PipelineEvent::Finished(outcome) => {
+                self.control = None;
+                self.paused = false;
+                // Lines that never finished get no new audio: those that still have a
+                // take go back to how they were, the rest to idle
+                let mut previous_statuses = std::mem::take(&mut self.previous_statuses);
+                for line in lines
+                    .iter_mut()
+                    .filter(|l| matches!(l.status, SynthesisStatus::Queued | SynthesisStatus::Working))
+                {
+                    line.status = match previous_statuses.remove(&line.id) {
+                        Some(previous) if line.selected_take().is_some() => previous,
+                        _ => SynthesisStatus::Idle,
+                    };
+                }
+                self.error = match outcome {
+                    RunOutcome::Completed => None,
+                    RunOutcome::CompletedWithFailures(failed) => Some(format!(
+                        "{} line(s) failed. See the failure report below to retry them.",
+                        failed
+                    )),
+                    RunOutcome::Stopped(error_msg) => Some(error_msg.clone()),
+                    RunOutcome::Cancelled => Some(
+                        "Synthesis cancelled. Finished lines keep their audio; Synthesize continues from the first line that isn't done.".to_string(),
+                    ),
+                };
+            }
+        }
+    }
+}
+
+/// Checks that every line in `lines` can be synthesized: each needs a known
+/// character, and there must be something to synthesize at all.
+pub fn validate_lines(lines: &[ScriptLine]) -> Result<(), String> {
+    let unknown_chars: Vec<String> = lines
+        .iter()
+        .filter(|line| line.needs_synthesis() && line.character_id.is_none())
+        .map(|line| line.character_name.clone())
+        .collect();
+    if !unknown_chars.is_empty() {
+        return Err(format!(
+            "Unknown character(s): {}. Please add them in Character Management first.",
+            unknown_chars.join(", ")
+        ));
+    }
+
+    if !lines.iter().any(|line| line.needs_synthesis()) {
+        return Err("No dialogue lines to synthesize.".to_string());
+    }
+    Ok(())
+}
+
+/// IDs of the lines in `range` to synthesize. Lines whose audio survived earlier
+/// edits are skipped; once every line is done, the whole range is rendered afresh.
+pub fn pending_lines(lines: &[ScriptLine], range: Range<usize>) -> Vec<String> {
+    let range = range.start.min(lines.len())..range.end.min(lines.len());
+    let synthesizable = lines[range].iter().filter(|line| line.needs_synthesis());
+    let pending: Vec<String> = synthesizable
+        .clone()
+        .filter(|line| !is_synthesized(line))
+        .map(|line| line.id.clone())
+        .collect();
+    if pending.is_empty() {
+        synthesizable.map(|line| line.id.clone()).collect()
+    } else {
+        pending
+    }
+}
+
+/// Whether a line's audio from an earlier run is still on disk.
+fn is_synthesized(line: &ScriptLine) -> bool {
+    line.status == SynthesisStatus::Done && line.output_path.as_ref().is_some_and(|p| Path::new(p).exists())
+}
+
+/// The voice reference for a line's character, or the status and message to
+/// report when there is none.
+fn voice_reference(line: &ScriptLine, characters: &[Character]) -> Result<String, (String, String)> {
+    let Some(character) = characters.iter().find(|c| Some(&c.id) == line.character_id.as_ref()) else {
+        // This shouldn't happen since lines are validated before synthesis
+        return Err((
+            "Character not found".to_string(),
+            format!("Character '{}' not found in character list", line.character_name),
+        ));
+    };
+    character.voice_path.clone().ok_or_else(|| {
+        (
+            "No voice reference".to_string(),
+            format!("Character '{}' has no voice reference audio.", character.name),
+        )
+    })
+}
+
+/// Parameters for a line, each taken from the first that sets it: the line's inline
+/// overrides, its character's defaults, then the run's `cfg_weight` and `exaggeration`.
+pub fn resolve_params(line: &ScriptLine, characters: &[Character], cfg_weight: f32, exaggeration: f32) -> SynthesisParams {
+    let defaults = characters
+        .iter()
+        .find(|c| Some(&c.id) == line.character_id.as_ref())
+        .map(|c| c.synthesis_defaults.clone())
+        .unwrap_or_default();
+    SynthesisParams {
+        cfg_weight: line.overrides.cfg_weight.or(defaults.cfg_weight).unwrap_or(cfg_weight),
+        exaggeration: line.overrides.exaggeration.or(defaults.exaggeration).unwrap_or(exaggeration),
+        model: defaults.model.unwrap_or_else(|| SYNTHESIS_MODEL.to_string()),
+        seed: defaults.seed,
+    }
+}
+
+/// Creates a fresh directory for one run's clips, `<output>/<project ID>/<start time>`,
+/// with a numeric suffix when another run started in the same second. The directory
+/// is claimed by creating it, so two runs never share one.
+fn run_directory(output_dir: &Path, project_id: Option<&str>, started: DateTime<Local>) -> Result<PathBuf, String> {
+    let project_dir = output_dir.join(project_id.unwrap_or("default"));
+    std::fs::create_dir_all(&project_dir).map_err(|e| format!("Failed to create output directory: {}", e))?;
+    let stamp = started.format("%Y-%m-%d_%H-%M-%S").to_string();
+    let mut dir = project_dir.join(&stamp);
+    let mut suffix = 2;
+    loop {
+        match std::fs::create_dir(&dir) {
+            Ok(()) => return Ok(dir),
+            Err(e) if e.kind() == std::io::ErrorKind::AlreadyExists => {
+                dir = project_dir.join(format!("{}_{}", stamp, suffix));
+                suffix += 1;
+            }
+            Err(e) => return Err(format!("Failed to create output directory {}: {}", dir.display(), e)),
+        }
+    }
+}
+
+/// A manifest entry for a line, before any takes are recorded.
+fn manifest_line(index: usize, line: &ScriptLine, voice_reference: Option<String>, digest: Option<String>) -> ManifestLine {
+    ManifestLine {
+        index,
+        line_id: line.id.clone(),
+        character_id: line.character_id.clone(),
+        character_name: line.character_name.clone(),
+        text: line.text.clone(),
+        voice_reference,
+        voice_reference_sha256: digest,
+        takes: Vec::new(),
+        error: None,
+    }
+}
+
+/// Synthesizes takes `takes` of a line into `output_dir`, each with its own file
+/// and seed, and records each for the manifest. Stops at the first failure, keeping
+/// the takes made before it.
+async fn synthesize_takes(
+    line: &ScriptLine,
+    voice_path: &str,
+    output_dir: &Path,
+    params: &SynthesisParams,
+    takes: Range<usize>,
+    settings: &Settings,
+    project: &ProjectConfig,
+) -> Result<Vec<(Take, ManifestTake)>, String> {
+    let mut done = Vec::new();
+    for take in takes {
+        let params = params.for_take(take);
+        let file = format!("line_{}_take{}.wav", line.id, take + 1);
+        let output_path = output_dir.join(&file);
+        // A later take without a fixed seed asks for a fresh rendition, not the cached one
+        let use_cache = take == 0 || params.seed.is_some();
+        let (started_at, timer) = (Utc::now(), Instant::now());
+        match synthesize_line(line, voice_path, &output_path, &params, use_cache, settings, project).await {
+            Ok(path) => {
+                let record = ManifestTake {
+                    file,
+                    cfg_weight: params.cfg_weight,
+                    exaggeration: params.exaggeration,
+                    model: params.model.clone(),
+                    seed: params.seed,
+                    duration_seconds: wav_duration(&output_path).unwrap_or_default(),
+                    started_at,
+                    synthesis_seconds: timer.elapsed().as_secs_f32(),
+                };
+                done.push((Take { path, params }, record));
+            }
+            Err(e) if done.is_empty() => return Err(e),
+            Err(e) => {
+                eprintln!("   ⚠️ Keeping {} take(s); the next failed: {}", done.len(), e);
+                break;
+            }
+        }
+    }
+    Ok(done)
+}
+
+/// Synthesizes one script line to `output_path`. Lines with several paragraphs are
+/// synthesized paragraph by paragraph and joined with a short pause; paragraphs
+/// longer than `max_chunk_chars` are split into chunks that are joined back to back.
+/// Pronunciation rules and normalization rewrite only the text sent to the model,
+/// not `line` itself. With `use_cache`, requests already in the synthesis cache are
+/// served from disk.
+async fn synthesize_line(
+    line: &ScriptLine,
+    voice_path: &str,
+    output_path: &Path,
+    params: &SynthesisParams,
+    use_cache: bool,
+    settings: &Settings,
+    project: &ProjectConfig,
+) -> Result<String, String> {
+    let output_path_str = output_path.to_string_lossy().to_string();
+    let max_chunk_chars = settings.max_chunk_chars;
+    let cache = SynthesisCache::in_output_dir(Path::new(&settings.output_directory), settings.cache_limit_mb);
+    // Without a readable reference there is nothing to key on, so skip the cache
+    let reference_digest = use_cache
+        .then(|| file_digest(Path::new(voice_path)))
+        .and_then(|digest| {
+            digest
+                .inspect_err(|e| eprintln!("   ⚠️ Synthesis cache disabled for this line: {}", e))
+                .ok()
+        });
+    let cached = reference_digest.as_deref().map(|digest| (&cache, digest));
+
+    // Rewrite the text the model hears, then split paragraphs further into
+    // chunks the model can handle in one request
+    let paragraphs: Vec<Vec<String>> = line
+        .paragraphs()
+        .iter()
+        .map(|paragraph| chunk_text(&spoken_text(paragraph, project), max_chunk_chars))
+        .collect();
+    if let [text] = paragraphs.concat().as_slice() {
+        return synthesize_cached(text, voice_path, output_path, params, cached, settings.max_retries).await;
+    }
+
+    let mut segments = Vec::new();
+    let mut part_paths = Vec::new();
+    for (i, chunks) in paragraphs.iter().enumerate() {
+        if i > 0 {
+            segments.push(AudioSegment::Silence(PARAGRAPH_PAUSE_SECONDS));
+        }
+        for (j, chunk) in chunks.iter().enumerate() {
+            let part_path = output_path.with_extension(format!("p{}_{}.wav", i, j));
+            synthesize_cached(chunk, voice_path, &part_path, params, cached, settings.max_retries).await?;
+            segments.push(AudioSegment::Clip(part_path.clone()));
+            part_paths.push(part_path);
+        }
+    }
+
+    let combined = combine_segments(segments, output_path.to_path_buf());
+    for part_path in part_paths {
+        let _ = std::fs::remove_file(part_path);
+    }
+    combined.map(|_| output_path_str)
+}
+
+/// Synthesizes one request, copying the clip from `cache` on a hit and storing
+/// fresh clips in it. `cache` pairs the cache with the voice reference's digest.
+/// Transient failures are retried up to `max_retries` times.
+async fn synthesize_cached(
+    text: &str,
+    voice_path: &str,
+    output_path: &Path,
+    params: &SynthesisParams,
+    cache: Option<(&SynthesisCache, &str)>,
+    max_retries: u32,
+) -> Result<String, String> {
+    let output_path_str = output_path.to_string_lossy().to_string();
+    let entry = cache.map(|(cache, digest)| {
+        (cache, SynthesisCache::key(text, digest, params))
+    });
+
+    if entry.as_ref().is_some_and(|(cache, key)| cache.get(key, output_path)) {
+        return Ok(output_path_str);
+    }
+
+    let path = synthesize_with_retries(text, voice_path, output_path_str, params, max_retries).await?;
+
+    if let Some(Err(e)) = entry.map(|(cache, key)| cache.put(&key, output_path)) {
+        eprintln!("   ⚠️ {}", e);
+    }
+    Ok(path)
+}
+
+/// Calls the synthesis backend, retrying connection errors and 5xx responses with
+/// exponential backoff. Other failures, like a missing reference, are returned at once.
+async fn synthesize_with_retries(
+    text: &str,
+    voice_path: &str,
+    output_path: String,
+    params: &SynthesisParams,
+    max_retries: u32,
+) -> Result<String, String> {
+    let mut attempt = 0;
+    loop {
+        let result = synthesize_audio(
+            text.to_string(),
+            voice_path.to_string(),
+            output_path.clone(),
+            params,
+        ).await;
+        match result {
+            Err(e) if attempt < max_retries && is_transient_error(&e) => {
+                let delay = RETRY_BASE_DELAY * 2u32.pow(attempt);
+                attempt += 1;
+                eprintln!("   🔁 {} (retry {}/{} in {:?})", e, attempt, max_retries, delay);
+                tokio::time::sleep(delay).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+/// Builds the export segments for a run of lines: clips, pauses as silence and
+/// scene headings as chapter markers.
+pub fn collect_segments(lines: &[ScriptLine]) -> Vec<AudioSegment> {
+    lines
+        .iter()
+        .filter_map(|line| match &line.kind {
+            LineKind::Pause { seconds } => Some(AudioSegment::Silence(*seconds)),
+            LineKind::Scene { title } => Some(AudioSegment::Marker(title.clone())),
+            _ => line.output_path.as_ref().map(|p| AudioSegment::Clip(PathBuf::from(p))),
+        })
+        .collect()
+}
+
+/// The text the voice model is given for `text`: pronunciation respellings first, so
+/// they win over the generic expansions, then tabletop normalization if enabled.
+pub fn spoken_text(text: &str, project: &ProjectConfig) -> String {
+    let text = apply_pronunciations(text, &project.pronunciations);
+    if project.normalize_text {
+        normalize_text(&text)
+    } else {
+        text
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use uuid::Uuid;
+
+    /// A two-line script whose character has no voice reference, so every line
+    /// fails before any request is made.
+    fn voiceless_pipeline(continue_on_error: bool) -> (SynthesisPipeline, std::path::PathBuf) {
+        let character = Character::new("Gandalf".to_string());
+        let lines = vec![
+            ScriptLine::new("Gandalf".to_string(), "Fly!".to_string(), Some(character.id.clone())),
+            ScriptLine::pause(1.0),
+            ScriptLine::new("Gandalf".to_string(), "You fools!".to_string(), Some(character.id.clone())),
+        ];
+        let dir = std::env::temp_dir().join(format!("test_pipeline_{}", Uuid::new_v4()));
+        let settings = Settings {
+            output_directory: dir.to_string_lossy().to_string(),
+            continue_on_error,
+            ..Settings::default()
+        };
+        let pipeline = SynthesisPipeline::new(lines, 0..3, vec![character], settings, ProjectConfig::default()).unwrap();
+        (pipeline, dir)
+    }
+
+    async fn collect_events(pipeline: SynthesisPipeline, control: RunControl) -> Vec<PipelineEvent> {
+        let mut receiver = pipeline.spawn(control);
+        let mut events = Vec::new();
+        while let Some(event) = receiver.recv().await {
+            events.push(event);
+        }
+        events
+    }
+
+    #[tokio::test]
+    async fn test_pipeline_stops_or_continues_on_failure() {
+        let (pipeline, dir) = voiceless_pipeline(false);
+        let ids = pipeline.pending().to_vec();
+        assert_eq!(ids.len(), 2);
+
+        let events = collect_events(pipeline.clone(), RunControl::new()).await;
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0], PipelineEvent::Queued { line_ids: ids.clone() });
+        assert!(matches!(&events[1], PipelineEvent::Failed { index: 0, reason, .. } if reason == "No voice reference"));
+        assert!(matches!(&events[2], PipelineEvent::Finished(RunOutcome::Stopped(_))));
+
+        // The subscriber's lines end up failed or back to idle
+        let mut lines = pipeline.lines.clone();
+        let mut status = RunStatus::started(RunControl::new(), 0);
+        for event in &events {
+            status.apply(event, &mut lines);
+        }
+        assert!(!status.is_running());
+        assert!(status.error.is_some());
+        assert_eq!(lines[0].status, SynthesisStatus::Error("No voice reference".to_string()));
+        assert_eq!(lines[2].status, SynthesisStatus::Idle);
+
+        let (pipeline, other_dir) = voiceless_pipeline(true);
+        let run_dir = pipeline.run_dir().to_path_buf();
+        assert!(run_dir.starts_with(other_dir.join("default")));
+        let events = collect_events(pipeline, RunControl::new()).await;
+        assert_eq!(events.len(), 4);
+        assert_eq!(events[3], PipelineEvent::Finished(RunOutcome::CompletedWithFailures(2)));
+
+        // The run's manifest records both failed lines in script order
+        let manifest: RenderManifest =
+            serde_json::from_str(&std::fs::read_to_string(run_dir.join(MANIFEST_FILE_NAME)).unwrap()).unwrap();
+        assert_eq!(manifest.outcome, "completed with 2 failed line(s)");
+        assert_eq!(manifest.lines.iter().map(|l| l.index).collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(manifest.lines[1].text, "You fools!");
+        assert_eq!(manifest.lines[1].error.as_deref(), Some("No voice reference"));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        std::fs::remove_dir_all(&other_dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_pipeline_starts_nothing() {
+        let (pipeline, dir) = voiceless_pipeline(true);
+        let control = RunControl::new();
+        control.cancel();
+
+        let events = collect_events(pipeline, control).await;
+        assert_eq!(events.last(), Some(&PipelineEvent::Finished(RunOutcome::Cancelled)));
+        assert!(!events.iter().any(|e| matches!(e, PipelineEvent::Failed { .. } | PipelineEvent::Started { .. })));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_pending_and_validation() {
+        let mut lines = vec![
+            ScriptLine::new("Gandalf".to_string(), "Fly!".to_string(), Some("g".to_string())),
+            ScriptLine::scene("Moria".to_string()),
+            ScriptLine::new("Gandalf".to_string(), "You fools!".to_string(), Some("g".to_string())),
+        ];
+        assert_eq!(pending_lines(&lines, 0..10), vec![lines[0].id.clone(), lines[2].id.clone()]);
+        assert_eq!(pending_lines(&lines, 1..3), vec![lines[2].id.clone()]);
+        // Exact IDs keep script order and skip scenes and lines not asked for
+        let ids = [lines[2].id.clone(), lines[1].id.clone()];
+        let dir = std::env::temp_dir().join(format!("test_pending_{}", Uuid::new_v4()));
+        let settings = Settings { output_directory: dir.to_string_lossy().to_string(), ..Settings::default() };
+        let pipeline = SynthesisPipeline::for_lines(lines.clone(), &ids, Vec::new(), settings, ProjectConfig::default()).unwrap();
+        assert_eq!(pipeline.pending(), [lines[2].id.clone()]);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(validate_lines(&lines).is_ok());
+        assert!(validate_lines(&lines[1..2]).is_err());
+
+        lines[2].character_id = None;
+        assert!(validate_lines(&lines).unwrap_err().contains("Gandalf"));
+    }
+
+    #[test]
+    fn test_runs_started_together_get_their_own_directories() {
+        let dir = std::env::temp_dir().join(format!("test_run_dirs_{}", Uuid::new_v4()));
+        let started = Local::now();
+        let first = run_directory(&dir, Some("project"), started).unwrap();
+        let second = run_directory(&dir, Some("project"), started).unwrap();
+
+        assert_ne!(first, second);
+        assert!(first.is_dir() && second.is_dir());
+        assert!(second.starts_with(dir.join("project")));
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn test_params_resolve_line_then_character_then_global() {
+        let mut character = Character::new("Strahd".to_string());
+        character.synthesis_defaults.exaggeration = Some(0.9);
+        character.synthesis_defaults.cfg_weight = Some(0.2);
+        character.synthesis_defaults.seed = Some(7);
+        let characters = vec![character.clone()];
+
+        let mut line = ScriptLine::new("Strahd".to_string(), "Welcome.".to_string(), Some(character.id.clone()));
+        line.overrides.cfg_weight = Some(0.4);
+        let params = resolve_params(&line, &characters, 0.5, 0.5);
+        assert_eq!(params.cfg_weight, 0.4);
+        assert_eq!(params.exaggeration, 0.9);
+        assert_eq!(params.model, SYNTHESIS_MODEL);
+        assert_eq!(params.seed, Some(7));
+
+        let stranger = ScriptLine::new("Ismark".to_string(), "Run.".to_string(), Some("i".to_string()));
+        let params = resolve_params(&stranger, &characters, 0.3, 0.6);
+        assert_eq!((params.cfg_weight, params.exaggeration, params.seed), (0.3, 0.6, None));
+    }
+
+    #[test]
+    fn test_cancelled_rerender_keeps_done_lines() {
+        let params = SynthesisParams { cfg_weight: 0.5, exaggeration: 0.5, model: SYNTHESIS_MODEL.to_string(), seed: None };
+        let mut lines: Vec<ScriptLine> = ["Fly!", "You fools!", "Run."]
+            .iter()
+            .map(|text| ScriptLine::new("Gandalf".to_string(), text.to_string(), Some("g".to_string())))
+            .collect();
+        for line in &mut lines[..2] {
+            line.takes.push(Take { path: format!("{}.wav", line.id), params: params.clone() });
+            line.select_take(0);
+        }
+        let ids: Vec<String> = lines.iter().map(|l| l.id.clone()).collect();
+
+        let mut status = RunStatus::started(RunControl::new(), 0);
+        status.apply(&PipelineEvent::Queued { line_ids: ids.clone() }, &mut lines);
+        status.apply(&PipelineEvent::Started { index: 0, line_id: ids[0].clone() }, &mut lines);
+        assert_eq!(lines[1].status, SynthesisStatus::Queued);
+        status.apply(&PipelineEvent::Finished(RunOutcome::Cancelled), &mut lines);
+
+        assert_eq!(lines[0].status, SynthesisStatus::Done);
+        assert_eq!(lines[1].status, SynthesisStatus::Done);
+        assert_eq!(lines[1].selected_take(), Some(0));
+        assert_eq!(lines[2].status, SynthesisStatus::Idle);
+    }
+
+    #[test]
+    fn test_failed_regenerate_keeps_selected_take() {
+        let params = SynthesisParams { cfg_weight: 0.5, exaggeration: 0.5, model: SYNTHESIS_MODEL.to_string(), seed: None };
+        let mut lines = vec![ScriptLine::new("Gandalf".to_string(), "Fly!".to_string(), Some("g".to_string()))];
+        lines[0].takes.push(Take { path: "take1.wav".to_string(), params });
+        lines[0].select_take(0);
+        let id = lines[0].id.clone();
+
+        let mut status = RunStatus::started(RunControl::new(), 0);
+        status.apply(&PipelineEvent::Queued { line_ids: vec![id.clone()] }, &mut lines);
+        status.apply(&PipelineEvent::Started { index: 0, line_id: id.clone() }, &mut lines);
+        let failed = PipelineEvent::Failed { index: 0, line_id: id, reason: "Server error: 500".to_string() };
+        status.apply(&failed, &mut lines);
+        status.apply(&PipelineEvent::Finished(RunOutcome::CompletedWithFailures(1)), &mut lines);
+
+        assert_eq!(lines[0].status, SynthesisStatus::Done);
+        assert_eq!(lines[0].output_path.as_deref(), Some("take1.wav"));
+        assert_eq!(lines[0].take_error.as_deref(), Some("Server error: 500"));
+    }
+}
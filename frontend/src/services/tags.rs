@@ -0,0 +1,105 @@
+/// Paralinguistic tags the voice model understands, in the order the insert buttons
+/// show them. Written in scripts inside brackets, e.g. `[sigh]`.
+pub const PARALINGUISTIC_TAGS: [&str; 9] = [
+    "clear throat", "sigh", "shush", "cough", "groan", "sniff", "gasp", "chuckle", "laugh",
+];
+
+/// Whether `name` is a known tag, ignoring case and surrounding whitespace.
+pub fn is_known_tag(name: &str) -> bool {
+    let name = normalize(name);
+    PARALINGUISTIC_TAGS.contains(&name.as_str())
+}
+
+/// Maps a tag written as an inflected verb, like `sighs`, `laughing` or
+/// `clears throat`, to the known tag it means.
+pub fn canonical_tag(name: &str) -> Option<&'static str> {
+    let name = normalize(name);
+    let mut words = name.split(' ');
+    let first = words.next()?;
+    let rest: Vec<&str> = words.collect();
+
+    let mut stems = vec![first.to_string()];
+    for suffix in ["s", "es", "ing", "ed", "d"] {
+        if let Some(stem) = first.strip_suffix(suffix) {
+            stems.push(stem.to_string());
+        }
+    }
+    // `chuckling` → `chuckle`
+    if let Some(stem) = first.strip_suffix("ing") {
+        stems.push(format!("{}e", stem));
+    }
+
+    stems.iter().find_map(|stem| {
+        let candidate = std::iter::once(stem.as_str())
+            .chain(rest.iter().copied())
+            .collect::<Vec<_>>()
+            .join(" ");
+        PARALINGUISTIC_TAGS.iter().copied().find(|tag| *tag == candidate)
+    })
+}
+
+/// The known tag an unknown one was probably meant to be: an inflected form
+/// (`laughs`), or a misspelling within a couple of edits (`cough` for `cuogh`).
+pub fn suggest_tag(name: &str) -> Option<&'static str> {
+    if let Some(tag) = canonical_tag(name) {
+        return Some(tag);
+    }
+
+    let name = normalize(name);
+    let max_distance = ((name.chars().count() + 1) / 3).clamp(1, 2);
+    PARALINGUISTIC_TAGS
+        .iter()
+        .copied()
+        .map(|tag| (edit_distance(&name, tag), tag))
+        .filter(|(distance, _)| *distance <= max_distance)
+        .min_by_key(|(distance, _)| *distance)
+        .map(|(_, tag)| tag)
+}
+
+fn normalize(name: &str) -> String {
+    name.split_whitespace().collect::<Vec<_>>().join(" ").to_lowercase()
+}
+
+/// Levenshtein distance between two strings, by characters.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_known_and_inflected_tags() {
+        assert!(is_known_tag("sigh"));
+        assert!(is_known_tag(" Clear  Throat "));
+        assert!(!is_known_tag("laughs"));
+
+        assert_eq!(canonical_tag("laughs"), Some("laugh"));
+        assert_eq!(canonical_tag("chuckling"), Some("chuckle"));
+        assert_eq!(canonical_tag("clears throat"), Some("clear throat"));
+        assert_eq!(canonical_tag("to Frodo"), None);
+    }
+
+    #[test]
+    fn test_suggestions() {
+        assert_eq!(suggest_tag("laughing"), Some("laugh"));
+        assert_eq!(suggest_tag("cuogh"), Some("cough"));
+        assert_eq!(suggest_tag("sniffle"), Some("sniff"));
+        assert_eq!(suggest_tag("clear thraot"), Some("clear throat"));
+        assert_eq!(suggest_tag("applause"), None);
+        assert_eq!(suggest_tag("hm"), None);
+    }
+}
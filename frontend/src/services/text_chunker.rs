@@ -0,0 +1,135 @@
+/// Splits text into chunks of at most `max_chars` characters for synthesis.
+///
+/// Breaks prefer sentence ends (`.`, `!`, `?`, `…`), then clause boundaries
+/// (`,`, `;`, `:`, dashes), then any space, as long as the chunk before the break
+/// is at least half the limit. Paralinguistic tags like `[clear throat]` are never
+/// split, and a single word or tag longer than the limit becomes its own chunk.
+/// Whitespace is collapsed to single spaces. A `max_chars` of 0 disables chunking.
+pub fn chunk_text(text: &str, max_chars: usize) -> Vec<String> {
+    let atoms = split_atoms(text);
+    if max_chars == 0 || joined_len(&atoms) <= max_chars {
+        return if atoms.is_empty() { Vec::new() } else { vec![atoms.join(" ")] };
+    }
+
+    let mut chunks = Vec::new();
+    let mut current: Vec<&str> = Vec::new();
+    for atom in atoms {
+        current.push(atom);
+        while current.len() > 1 && joined_len(&current) > max_chars {
+            // Everything but the newest atom fits, so break somewhere before it
+            let cut = best_cut(&current[..current.len() - 1], max_chars);
+            chunks.push(current[..cut].join(" "));
+            current.drain(..cut);
+        }
+    }
+    if !current.is_empty() {
+        chunks.push(current.join(" "));
+    }
+
+    chunks
+}
+
+/// Splits text on whitespace, keeping bracketed tags together as one atom.
+fn split_atoms(text: &str) -> Vec<&str> {
+    let mut atoms = Vec::new();
+    let mut start: Option<usize> = None;
+    let mut in_tag = false;
+
+    for (i, c) in text.char_indices() {
+        match c {
+            '[' => in_tag = true,
+            ']' => in_tag = false,
+            _ => {}
+        }
+        if c.is_whitespace() && !in_tag {
+            if let Some(s) = start.take() {
+                atoms.push(&text[s..i]);
+            }
+        } else if start.is_none() {
+            start = Some(i);
+        }
+    }
+    if let Some(s) = start {
+        atoms.push(&text[s..]);
+    }
+
+    atoms
+}
+
+/// Length in characters of the atoms joined with single spaces.
+fn joined_len(atoms: &[&str]) -> usize {
+    atoms.iter().map(|a| a.chars().count()).sum::<usize>() + atoms.len().saturating_sub(1)
+}
+
+/// Picks how many leading atoms to put in the next chunk: the strongest boundary
+/// that leaves a chunk of at least half the limit, or as many atoms as fit.
+fn best_cut(atoms: &[&str], max_chars: usize) -> usize {
+    let mut best = (0, atoms.len());
+    for cut in 1..=atoms.len() {
+        if joined_len(&atoms[..cut]) * 2 < max_chars {
+            continue;
+        }
+        let strength = boundary_strength(atoms[cut - 1]);
+        if strength > 0 && strength >= best.0 {
+            best = (strength, cut);
+        }
+    }
+    best.1
+}
+
+/// 2 after a sentence end, 1 after a clause boundary, 0 otherwise.
+fn boundary_strength(atom: &str) -> u8 {
+    let word = atom.trim_end_matches(['"', '\'', '”', '’', ')']);
+    if word.ends_with(['.', '!', '?', '…']) {
+        2
+    } else if word.ends_with([',', ';', ':', '—', '–']) || word == "-" {
+        1
+    } else {
+        0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_short_text_is_one_chunk() {
+        assert_eq!(chunk_text("  You shall\nnot pass! ", 100), vec!["You shall not pass!"]);
+        assert!(chunk_text("   ", 100).is_empty());
+        assert_eq!(chunk_text("No limit at all.", 0), vec!["No limit at all."]);
+    }
+
+    #[test]
+    fn test_prefers_sentence_then_clause_boundaries() {
+        let text = "All we have to decide is what to do. With the time that is given to us, we decide.";
+        assert_eq!(
+            chunk_text(text, 60),
+            vec!["All we have to decide is what to do.", "With the time that is given to us, we decide."]
+        );
+
+        let text = "With the time that is given to us, whatever may come, we decide what to do";
+        assert_eq!(
+            chunk_text(text, 55),
+            vec!["With the time that is given to us, whatever may come,", "we decide what to do"]
+        );
+    }
+
+    #[test]
+    fn test_never_splits_tags_and_respects_limit() {
+        let text = "Well [clear throat] I suppose that is that [sigh] and so we go on and on";
+        let chunks = chunk_text(text, 20);
+
+        assert!(chunks.iter().all(|c| c.chars().count() <= 20));
+        assert!(chunks.iter().any(|c| c.contains("[clear throat]")));
+        assert_eq!(chunks.join(" "), text);
+    }
+
+    #[test]
+    fn test_overlong_word_is_its_own_chunk() {
+        assert_eq!(
+            chunk_text("A Llanfairpwllgwyngyll day", 10),
+            vec!["A", "Llanfairpwllgwyngyll", "day"]
+        );
+    }
+}
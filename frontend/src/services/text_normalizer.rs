@@ -0,0 +1,259 @@
+use regex::{Captures, Regex};
+
+const ONES: [&str; 20] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine", "ten",
+    "eleven", "twelve", "thirteen", "fourteen", "fifteen", "sixteen", "seventeen", "eighteen", "nineteen",
+];
+
+const TENS: [&str; 10] = [
+    "", "", "twenty", "thirty", "forty", "fifty", "sixty", "seventy", "eighty", "ninety",
+];
+
+const SCALES: [(u64, &str); 4] = [
+    (1_000_000_000_000, "trillion"),
+    (1_000_000_000, "billion"),
+    (1_000_000, "million"),
+    (1_000, "thousand"),
+];
+
+/// Coin abbreviations and the metal they name.
+const CURRENCIES: [(&str, &str); 5] = [
+    ("cp", "copper"),
+    ("sp", "silver"),
+    ("ep", "electrum"),
+    ("gp", "gold"),
+    ("pp", "platinum"),
+];
+
+/// Common tabletop abbreviations, matched case-sensitively as whole words.
+const ABBREVIATIONS: [(&str, &str); 14] = [
+    ("AC", "armor class"),
+    ("DC", "difficulty class"),
+    ("HP", "hit points"),
+    ("XP", "experience points"),
+    ("Lvl", "level"),
+    ("lvl", "level"),
+    ("NPCs", "N P Cs"),
+    ("NPC", "N P C"),
+    ("PCs", "P Cs"),
+    ("PC", "P C"),
+    ("DM", "D M"),
+    ("GM", "G M"),
+    ("TPK", "T P K"),
+    ("D&D", "D and D"),
+];
+
+/// Rewrites tabletop shorthand into words the voice model reads naturally.
+///
+/// Expands, in this order: dice (`3d6+2` → "three d six plus two"), coins
+/// (`50 gp` → "fifty gold pieces"), ordinals (`3rd` → "third"), editions
+/// (`5e` → "fifth edition"), modifiers (`+2 longsword` → "plus two longsword"),
+/// percentages, the abbreviations in `ABBREVIATIONS` (`AC 15` → "armor class
+/// fifteen") and finally any remaining numbers, including `1,500` and `2.5`.
+/// Paralinguistic tags like `[sigh]` are left untouched.
+pub fn normalize_text(text: &str) -> String {
+    let tags = Regex::new(r"\[[^\]]*\]").unwrap();
+    let mut normalized = String::with_capacity(text.len());
+    let mut last = 0;
+    for tag in tags.find_iter(text) {
+        normalized.push_str(&normalize_prose(&text[last..tag.start()]));
+        normalized.push_str(tag.as_str());
+        last = tag.end();
+    }
+    normalized.push_str(&normalize_prose(&text[last..]));
+    normalized
+}
+
+/// Normalizes text that holds no tags; see `normalize_text`.
+fn normalize_prose(text: &str) -> String {
+    let dice = Regex::new(r"\b(\d*)[dD](\d+)(?:\s*([+-])\s*(\d+))?\b").unwrap();
+    let text = dice.replace_all(text, |c: &Captures| {
+        let mut spoken = match c.get(1).map(|m| m.as_str()).filter(|s| !s.is_empty()) {
+            Some(count) => format!("{} d {}", number_words_str(count), number_words_str(&c[2])),
+            None => format!("d {}", number_words_str(&c[2])),
+        };
+        if let (Some(sign), Some(modifier)) = (c.get(3), c.get(4)) {
+            spoken.push_str(&format!(" {} {}", sign_word(sign.as_str()), number_words_str(modifier.as_str())));
+        }
+        spoken
+    });
+
+    let coins = Regex::new(r"(?i)\b(\d[\d,]*)\s*(cp|sp|ep|gp|pp)\b").unwrap();
+    let text = coins.replace_all(&text, |c: &Captures| {
+        let abbreviation = c[2].to_lowercase();
+        let metal = CURRENCIES
+            .iter()
+            .find(|(abbr, _)| *abbr == abbreviation)
+            .map(|(_, metal)| *metal)
+            .unwrap_or_default();
+        let amount = c[1].replace(',', "");
+        let pieces = if amount == "1" { "piece" } else { "pieces" };
+        format!("{} {} {}", number_words_str(&amount), metal, pieces)
+    });
+
+    let ordinals = Regex::new(r"(?i)\b(\d+)(st|nd|rd|th)\b").unwrap();
+    let text = ordinals.replace_all(&text, |c: &Captures| match c[1].parse::<u64>() {
+        Ok(n) => ordinal_words(n),
+        Err(_) => c[0].to_string(),
+    });
+
+    let editions = Regex::new(r"\b(\d+)e\b").unwrap();
+    let text = editions.replace_all(&text, |c: &Captures| match c[1].parse::<u64>() {
+        Ok(n) => format!("{} edition", ordinal_words(n)),
+        Err(_) => c[0].to_string(),
+    });
+
+    let modifiers = Regex::new(r"(^|[\s(])([+-])(\d+)\b").unwrap();
+    let text = modifiers.replace_all(&text, |c: &Captures| {
+        format!("{}{} {}", &c[1], sign_word(&c[2]), number_words_str(&c[3]))
+    });
+
+    let percentages = Regex::new(r"\b(\d+(?:\.\d+)?)\s*%").unwrap();
+    let text = percentages.replace_all(&text, |c: &Captures| format!("{} percent", number_words_str(&c[1])));
+
+    // `Lvl.` before the table, so the period isn't left behind
+    let levels = Regex::new(r"\b(?:Lvl|lvl|LVL)\.").unwrap();
+    let mut text = levels.replace_all(&text, "level").into_owned();
+    // `\b` needs word characters on both ends, so anchor on non-word neighbours instead.
+    // The table lists longer forms first, so `NPCs` wins over `NPC`.
+    let alternatives: Vec<String> = ABBREVIATIONS.iter().map(|(abbr, _)| regex::escape(abbr)).collect();
+    let abbreviations = Regex::new(&format!(r"(^|[^\w&])({})($|[^\w&])", alternatives.join("|"))).unwrap();
+    // Run twice so adjacent matches that share a separator are both expanded
+    for _ in 0..2 {
+        text = abbreviations
+            .replace_all(&text, |c: &Captures| {
+                let expansion = ABBREVIATIONS.iter().find(|(abbr, _)| *abbr == &c[2]).map_or("", |(_, e)| *e);
+                format!("{}{}{}", &c[1], expansion, &c[3])
+            })
+            .into_owned();
+    }
+
+    let numbers = Regex::new(r"\b\d{1,3}(?:,\d{3})+(?:\.\d+)?\b|\b\d+(?:\.\d+)?\b").unwrap();
+    numbers
+        .replace_all(&text, |c: &Captures| number_words_str(&c[0]))
+        .into_owned()
+}
+
+/// Spells out a whole number, e.g. 1234 → "one thousand two hundred thirty-four".
+pub fn number_to_words(n: u64) -> String {
+    if n < 20 {
+        return ONES[n as usize].to_string();
+    }
+    if n < 100 {
+        let tens = TENS[(n / 10) as usize];
+        return match n % 10 {
+            0 => tens.to_string(),
+            ones => format!("{}-{}", tens, ONES[ones as usize]),
+        };
+    }
+    if n < 1000 {
+        let hundreds = format!("{} hundred", ONES[(n / 100) as usize]);
+        return match n % 100 {
+            0 => hundreds,
+            rest => format!("{} {}", hundreds, number_to_words(rest)),
+        };
+    }
+
+    let (scale, name) = SCALES.iter().copied().find(|(scale, _)| n >= *scale).unwrap();
+    let head = format!("{} {}", number_to_words(n / scale), name);
+    match n % scale {
+        0 => head,
+        rest => format!("{} {}", head, number_to_words(rest)),
+    }
+}
+
+/// Spells out an ordinal, e.g. 21 → "twenty-first".
+pub fn ordinal_words(n: u64) -> String {
+    let words = number_to_words(n);
+    let split = words.rfind([' ', '-']).map_or(0, |i| i + 1);
+    let (head, last) = words.split_at(split);
+    let last = match last {
+        "one" => "first".to_string(),
+        "two" => "second".to_string(),
+        "three" => "third".to_string(),
+        "five" => "fifth".to_string(),
+        "eight" => "eighth".to_string(),
+        "nine" => "ninth".to_string(),
+        "twelve" => "twelfth".to_string(),
+        other => match other.strip_suffix('y') {
+            Some(stem) => format!("{}ieth", stem),
+            None => format!("{}th", other),
+        },
+    };
+    format!("{}{}", head, last)
+}
+
+/// Spells out a number written as digits, with optional thousands separators and
+/// decimals read digit by digit ("2.5" → "two point five"). Numbers too large to
+/// spell are returned unchanged.
+fn number_words_str(digits: &str) -> String {
+    let digits = digits.replace(',', "");
+    let (whole, fraction) = match digits.split_once('.') {
+        Some((whole, fraction)) => (whole, Some(fraction)),
+        None => (digits.as_str(), None),
+    };
+    let Ok(n) = whole.parse::<u64>() else {
+        return digits.to_string();
+    };
+
+    let mut words = number_to_words(n);
+    if let Some(fraction) = fraction {
+        words.push_str(" point");
+        for digit in fraction.chars().filter_map(|c| c.to_digit(10)) {
+            words.push(' ');
+            words.push_str(ONES[digit as usize]);
+        }
+    }
+    words
+}
+
+fn sign_word(sign: &str) -> &'static str {
+    if sign == "-" { "minus" } else { "plus" }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expansion_table() {
+        let cases = [
+            ("Roll 3d6.", "Roll three d six."),
+            ("Roll a d20", "Roll a d twenty"),
+            ("It deals 2d8+3 damage", "It deals two d eight plus three damage"),
+            ("1d4 - 1", "one d four minus one"),
+            ("A +2 longsword", "A plus two longsword"),
+            ("a -1 penalty (+5 to hit)", "a minus one penalty (plus five to hit)"),
+            ("AC 15", "armor class fifteen"),
+            ("a DC 12 save", "a difficulty class twelve save"),
+            ("50 gp", "fifty gold pieces"),
+            ("1 gp and 12sp", "one gold piece and twelve silver pieces"),
+            ("1,500 GP", "one thousand five hundred gold pieces"),
+            ("Lvl 5 wizard", "level five wizard"),
+            ("lvl. 20", "level twenty"),
+            ("the 3rd, 21st and 112th", "the third, twenty-first and one hundred twelfth"),
+            ("40 HP, 300 XP", "forty hit points, three hundred experience points"),
+            ("The DM and the NPCs", "The D M and the N P Cs"),
+            ("D&D 5e", "D and D fifth edition"),
+            ("2.5 miles, 75% sure", "two point five miles, seventy-five percent sure"),
+            ("Word without numbers", "Word without numbers"),
+            ("[sigh] Nineteen", "[sigh] Nineteen"),
+            ("[sigh] DC 12 [DM aside 2]", "[sigh] difficulty class twelve [DM aside 2]"),
+        ];
+
+        for (input, expected) in cases {
+            assert_eq!(normalize_text(input), expected, "input: {}", input);
+        }
+    }
+
+    #[test]
+    fn test_number_words() {
+        assert_eq!(number_to_words(0), "zero");
+        assert_eq!(number_to_words(40), "forty");
+        assert_eq!(number_to_words(1_000_001), "one million one");
+        assert_eq!(number_to_words(2_345_678), "two million three hundred forty-five thousand six hundred seventy-eight");
+        assert_eq!(ordinal_words(1), "first");
+        assert_eq!(ordinal_words(20), "twentieth");
+        assert_eq!(ordinal_words(1000), "one thousandth");
+    }
+}
@@ -1,23 +1,51 @@
 use std::path::{Path, PathBuf};
-use std::io::BufReader;
-use std::fs::File;
+use std::io::{BufReader, Seek, SeekFrom, Write};
+use std::fs::{File, OpenOptions};
+
+/// One piece of a combined output file.
+#[derive(Debug, Clone, PartialEq)]
+pub enum AudioSegment {
+    /// A synthesized WAV clip.
+    Clip(PathBuf),
+    /// Silence of the given length in seconds, rendered in the clips' WAV format.
+    Silence(f32),
+    /// A named chapter marker at this point, written as a WAV cue point with a label.
+    Marker(String),
+}
 
 /// Combines multiple WAV files into a single output WAV file.
 /// All input files must have the same sample rate, channels, and bits per sample.
 pub fn combine_wavs(input_paths: Vec<PathBuf>, output_path: PathBuf) -> Result<(), String> {
-    if input_paths.is_empty() {
+    let segments = input_paths.into_iter().map(AudioSegment::Clip).collect();
+    combine_segments(segments, output_path)
+}
+
+/// Combines WAV clips and silent gaps, in order, into a single output WAV file.
+/// All clips must share one format; silences are written as sample-accurate runs
+/// of zero samples in that same format. Markers become `cue ` points labelled in a
+/// `LIST`/`adtl` chunk, which audio editors show as chapters or regions.
+pub fn combine_segments(segments: Vec<AudioSegment>, output_path: PathBuf) -> Result<(), String> {
+    let clip_paths: Vec<&PathBuf> = segments
+        .iter()
+        .filter_map(|segment| match segment {
+            AudioSegment::Clip(path) => Some(path),
+            AudioSegment::Silence(_) | AudioSegment::Marker(_) => None,
+        })
+        .collect();
+    
+    if clip_paths.is_empty() {
         return Err("No input files provided".to_string());
     }
     
     // Read the first file to get the audio format
-    let first_path = &input_paths[0];
+    let first_path = clip_paths[0];
     let first_reader = hound::WavReader::open(first_path)
         .map_err(|e| format!("Failed to open {}: {}", first_path.display(), e))?;
     
     let spec = first_reader.spec();
     
     // Validate all files have the same format
-    for path in &input_paths[1..] {
+    for path in &clip_paths[1..] {
         let reader = hound::WavReader::open(path)
             .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
         
@@ -33,55 +61,180 @@ pub fn combine_wavs(input_paths: Vec<PathBuf>, output_path: PathBuf) -> Result<(
     let mut writer = hound::WavWriter::create(&output_path, spec)
         .map_err(|e| format!("Failed to create output file: {}", e))?;
     
-    // Read and write samples from each file
-    for path in input_paths {
-        let mut reader = hound::WavReader::open(&path)
-            .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
-        
-        // Copy samples based on the sample format
-        match spec.sample_format {
-            hound::SampleFormat::Float => {
-                for sample in reader.samples::<f32>() {
-                    let sample = sample.map_err(|e| format!("Failed to read sample: {}", e))?;
-                    writer.write_sample(sample)
-                        .map_err(|e| format!("Failed to write sample: {}", e))?;
-                }
+    // Chapter markers as (sample frame, label)
+    let mut markers: Vec<(u32, String)> = Vec::new();
+    let mut frames: u64 = 0;
+    
+    for segment in segments {
+        match segment {
+            AudioSegment::Clip(path) => frames += copy_samples(&path, spec, &mut writer)? / spec.channels as u64,
+            AudioSegment::Silence(seconds) => frames += write_silence(seconds, spec, &mut writer)?,
+            AudioSegment::Marker(label) => markers.push((frames as u32, label)),
+        }
+    }
+    
+    writer.finalize()
+        .map_err(|e| format!("Failed to finalize output file: {}", e))?;
+    
+    if !markers.is_empty() {
+        append_markers(&output_path, &markers)?;
+    }
+    
+    Ok(())
+}
+
+/// Length of a WAV file in seconds.
+pub fn wav_duration(path: &Path) -> Result<f32, String> {
+    let reader = hound::WavReader::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    Ok(reader.duration() as f32 / reader.spec().sample_rate as f32)
+}
+
+/// Number of sample frames needed to fill `seconds` at the given sample rate.
+pub fn silence_frames(seconds: f32, sample_rate: u32) -> u64 {
+    (seconds.max(0.0) as f64 * sample_rate as f64).round() as u64
+}
+
+/// Copies every sample of a clip into the writer, returning the number of samples copied.
+fn copy_samples(
+    path: &Path,
+    spec: hound::WavSpec,
+    writer: &mut hound::WavWriter<std::io::BufWriter<File>>,
+) -> Result<u64, String> {
+    let mut reader: hound::WavReader<BufReader<File>> = hound::WavReader::open(path)
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))?;
+    
+    let count = reader.len() as u64;
+    
+    // Copy samples based on the sample format
+    match spec.sample_format {
+        hound::SampleFormat::Float => {
+            for sample in reader.samples::<f32>() {
+                let sample = sample.map_err(|e| format!("Failed to read sample: {}", e))?;
+                writer.write_sample(sample)
+                    .map_err(|e| format!("Failed to write sample: {}", e))?;
             }
-            hound::SampleFormat::Int => {
-                match spec.bits_per_sample {
-                    16 => {
-                        for sample in reader.samples::<i16>() {
-                            let sample = sample.map_err(|e| format!("Failed to read sample: {}", e))?;
-                            writer.write_sample(sample)
-                                .map_err(|e| format!("Failed to write sample: {}", e))?;
-                        }
-                    }
-                    32 => {
-                        for sample in reader.samples::<i32>() {
-                            let sample = sample.map_err(|e| format!("Failed to read sample: {}", e))?;
-                            writer.write_sample(sample)
-                                .map_err(|e| format!("Failed to write sample: {}", e))?;
-                        }
+        }
+        hound::SampleFormat::Int => {
+            match spec.bits_per_sample {
+                16 => {
+                    for sample in reader.samples::<i16>() {
+                        let sample = sample.map_err(|e| format!("Failed to read sample: {}", e))?;
+                        writer.write_sample(sample)
+                            .map_err(|e| format!("Failed to write sample: {}", e))?;
                     }
-                    _ => {
-                        return Err(format!("Unsupported bits per sample: {}", spec.bits_per_sample));
+                }
+                32 => {
+                    for sample in reader.samples::<i32>() {
+                        let sample = sample.map_err(|e| format!("Failed to read sample: {}", e))?;
+                        writer.write_sample(sample)
+                            .map_err(|e| format!("Failed to write sample: {}", e))?;
                     }
                 }
+                _ => {
+                    return Err(format!("Unsupported bits per sample: {}", spec.bits_per_sample));
+                }
             }
         }
     }
     
-    writer.finalize()
-        .map_err(|e| format!("Failed to finalize output file: {}", e))?;
+    Ok(count)
+}
+
+/// Writes `seconds` of silence, returning the number of frames written.
+fn write_silence(
+    seconds: f32,
+    spec: hound::WavSpec,
+    writer: &mut hound::WavWriter<std::io::BufWriter<File>>,
+) -> Result<u64, String> {
+    let frames = silence_frames(seconds, spec.sample_rate);
+    let samples = frames * spec.channels as u64;
+    
+    for _ in 0..samples {
+        let result = match (spec.sample_format, spec.bits_per_sample) {
+            (hound::SampleFormat::Float, _) => writer.write_sample(0.0f32),
+            (hound::SampleFormat::Int, 16) => writer.write_sample(0i16),
+            (hound::SampleFormat::Int, 32) => writer.write_sample(0i32),
+            _ => return Err(format!("Unsupported bits per sample: {}", spec.bits_per_sample)),
+        };
+        result.map_err(|e| format!("Failed to write sample: {}", e))?;
+    }
+    
+    Ok(frames)
+}
+
+/// Appends a `cue ` chunk and a `LIST`/`adtl` chunk of labels to a finished WAV
+/// file, then patches the RIFF size to cover them.
+fn append_markers(path: &Path, markers: &[(u32, String)]) -> Result<(), String> {
+    let mut cue = Vec::new();
+    cue.extend_from_slice(&(markers.len() as u32).to_le_bytes());
+    let mut adtl = b"adtl".to_vec();
+    
+    for (index, (frame, label)) in markers.iter().enumerate() {
+        let id = index as u32 + 1;
+        cue.extend_from_slice(&id.to_le_bytes());
+        cue.extend_from_slice(&frame.to_le_bytes());
+        cue.extend_from_slice(b"data");
+        cue.extend_from_slice(&0u32.to_le_bytes()); // chunk start
+        cue.extend_from_slice(&0u32.to_le_bytes()); // block start
+        cue.extend_from_slice(&frame.to_le_bytes());
+        
+        let mut labl = id.to_le_bytes().to_vec();
+        labl.extend_from_slice(label.as_bytes());
+        labl.push(0);
+        adtl.extend_from_slice(&riff_chunk(b"labl", &labl));
+    }
+    
+    let io_err = |e: std::io::Error| format!("Failed to write chapter markers: {}", e);
+    let mut file = OpenOptions::new().read(true).write(true).open(path).map_err(io_err)?;
+    let mut len = file.seek(SeekFrom::End(0)).map_err(io_err)?;
+    
+    // Chunks start on even offsets
+    if len % 2 == 1 {
+        file.write_all(&[0]).map_err(io_err)?;
+        len += 1;
+    }
+    let chunks = [riff_chunk(b"cue ", &cue), riff_chunk(b"LIST", &adtl)].concat();
+    file.write_all(&chunks).map_err(io_err)?;
+    
+    let riff_size = (len + chunks.len() as u64 - 8) as u32;
+    file.seek(SeekFrom::Start(4)).map_err(io_err)?;
+    file.write_all(&riff_size.to_le_bytes()).map_err(io_err)?;
     
     Ok(())
 }
 
+/// Encodes one RIFF chunk, padding odd-sized payloads to an even length.
+fn riff_chunk(id: &[u8; 4], payload: &[u8]) -> Vec<u8> {
+    let mut chunk = id.to_vec();
+    chunk.extend_from_slice(&(payload.len() as u32).to_le_bytes());
+    chunk.extend_from_slice(payload);
+    if payload.len() % 2 == 1 {
+        chunk.push(0);
+    }
+    chunk
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::fs;
     use std::path::PathBuf;
+    use uuid::Uuid;
+    
+    fn write_test_wav(path: &PathBuf, samples: &[i16]) {
+        let spec = hound::WavSpec {
+            channels: 2,
+            sample_rate: 8000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(path, spec).unwrap();
+        for &sample in samples {
+            writer.write_sample(sample).unwrap();
+        }
+        writer.finalize().unwrap();
+    }
     
     #[test]
     fn test_combine_empty_list() {
@@ -91,6 +244,77 @@ mod tests {
         assert_eq!(result.unwrap_err(), "No input files provided");
     }
     
-    // Additional tests would require creating actual WAV files
-    // For now, we'll rely on manual testing with real synthesis output
+    #[test]
+    fn test_combine_segments_inserts_silence() {
+        let dir = std::env::temp_dir().join(format!("test_combine_{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let first = dir.join("a.wav");
+        let second = dir.join("b.wav");
+        let output = dir.join("out.wav");
+        write_test_wav(&first, &[1, 1, 2, 2]);
+        write_test_wav(&second, &[3, 3]);
+        
+        combine_segments(
+            vec![
+                AudioSegment::Clip(first),
+                AudioSegment::Silence(0.00025), // 2 frames at 8 kHz
+                AudioSegment::Clip(second),
+            ],
+            output.clone(),
+        )
+        .unwrap();
+        
+        let mut reader = hound::WavReader::open(&output).unwrap();
+        assert_eq!(reader.spec().channels, 2);
+        let samples: Vec<i16> = reader.samples::<i16>().map(|s| s.unwrap()).collect();
+        assert_eq!(samples, vec![1, 1, 2, 2, 0, 0, 0, 0, 3, 3]);
+        
+        let _ = fs::remove_dir_all(dir);
+    }
+    
+    #[test]
+    fn test_silence_only_is_rejected() {
+        let result = combine_segments(vec![AudioSegment::Silence(1.0)], PathBuf::from("unused.wav"));
+        assert_eq!(result.unwrap_err(), "No input files provided");
+    }
+    
+    #[test]
+    fn test_markers_written_as_cue_points() {
+        let dir = std::env::temp_dir().join(format!("test_markers_{}", Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        let clip = dir.join("a.wav");
+        let output = dir.join("out.wav");
+        write_test_wav(&clip, &[1, 1, 2, 2, 3, 3]);
+        
+        combine_segments(
+            vec![
+                AudioSegment::Marker("Moria".to_string()),
+                AudioSegment::Clip(clip.clone()),
+                AudioSegment::Marker("Lothlórien".to_string()),
+                AudioSegment::Clip(clip),
+            ],
+            output.clone(),
+        )
+        .unwrap();
+        
+        // The audio is still readable and unchanged
+        let mut reader = hound::WavReader::open(&output).unwrap();
+        let samples: Vec<i16> = reader.samples::<i16>().map(|s| s.unwrap()).collect();
+        assert_eq!(samples, vec![1, 1, 2, 2, 3, 3, 1, 1, 2, 2, 3, 3]);
+        assert!((wav_duration(&output).unwrap() - 6.0 / 8000.0).abs() < 1e-6);
+        
+        let bytes = fs::read(&output).unwrap();
+        let riff_size = u32::from_le_bytes(bytes[4..8].try_into().unwrap()) as usize;
+        assert_eq!(riff_size, bytes.len() - 8);
+        
+        let cue = bytes.windows(4).position(|w| w == b"cue ").unwrap();
+        assert_eq!(u32::from_le_bytes(bytes[cue + 8..cue + 12].try_into().unwrap()), 2);
+        // The second cue point sits after the first clip's three frames
+        let second = cue + 12 + 24;
+        assert_eq!(u32::from_le_bytes(bytes[second + 4..second + 8].try_into().unwrap()), 3);
+        let label = "Lothlórien\0".as_bytes();
+        assert!(bytes.windows(label.len()).any(|w| w == label));
+        
+        let _ = fs::remove_dir_all(dir);
+    }
 }